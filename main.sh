@@ -3,21 +3,35 @@
 
 # Instructions
 # For each day:
+# `cargo run new <day> --title <title>` does steps 1, 2 and 4 from a template.
 # 1. Create src/dayxx.rs. Pad 0 for 1-9 so that files sort properly.
-#  Write a function like `pub fn part1(input: String) {}`
-# 2. In src/lib.rs, add a line with `pub mod dayxx;` as shown
-# 3. In src/lib.rs, add a case to the match, for example:
-#   ```
-#   1 => (day01::part1, day01::part2),
-#   2 => (day02::part1, noop),
-#   ```
-#   Use noop whenever a part is not yet implemented.
+#  Write a struct like `pub(crate) struct Day01;` implementing `Solution`
+#  with `DAY`, `TITLE`, `parse`, `part1` and `part2`.
+#  Set `PARTS` to 0 or 1 while parts are not yet implemented, they are skipped.
+# 2. In src/lib.rs, add `dayxx::DayXX,` to the `register_days!` list
+# 3. Run `cargo run list` to check which days and parts are registered
+#  `cargo run watch <day>` re-runs a day whenever its input file changes.
 # 4. Create inputs/dayxx.txt and add your puzzle input
-# 5. Update the argument below to the current day.
+# 5. Update the argument below to the current day, a range like `3-9` or `all`.
 #  If no arguments are given, user will be prompted for the day on stdin.
+#  `--inputs-dir <dir>` reads dayxx.txt files from another directory, and
+#  `--input <path>` runs a single day on any file (`-` reads stdin).
+#  `--jobs N` runs days on N threads (0 for every core), results stay in day order.
+#  `--param name=value` overrides a puzzle constant, `cargo run list` shows them.
+#  `--timeout <secs>` limits each part, panics and timeouts show up in the summary.
+#  Timings are appended to target/history.csv (`--history <path>`, `--no-history`), parts
+#  slower than their median by `--regression <percent>` (20) are flagged, and
+#  `cargo run history <day>` shows past runs. Runs of several days with `--jobs` above 1
+#  or with `--profile` are neither recorded nor compared. `--budget <secs>` flags days
+#  slower than that, `--budget <day>=<secs>` sets the budget of a single day.
+#  `--profile` adds allocation counts, bytes and peak live memory per parse/part.
+#  `--format json` prints a JSON record per part instead (NDJSON for several days).
+#  `--example` runs the examples a day returns from `Solution::examples` instead.
 # Your code will be passed the input and timed automatically.
+# `cargo test` also feeds every parser random and mutated example input, parsers must
+#  return errors rather than panic. `cargo +nightly fuzz run parse` (or `packet_order`)
+#  keeps fuzzing them with the targets in fuzz/, needs `cargo install cargo-fuzz`.
+# `cargo run gen <day> --size N --seed S` prints a random input for stress testing, the same
+#  seed gives the same input; without `--seed` one is picked and printed to stderr.
 
-cargo run 1
-cargo run 2
-cargo run 3
-cargo run 4
+cargo run all
@@ -0,0 +1,86 @@
+use std::{convert::TryFrom, fmt};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    // multi-line output, e.g. the CRT screen of day10
+    Image(Vec<String>),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Image(rows) => write!(f, "\n{}", rows.join("\n")),
+            Answer::Unsolved => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, i128, isize);
+
+// Too big for a `Number` above `i128::MAX`, still shown as digits
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        i128::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Number)
+    }
+}
+
+// `None` for a part that found no answer in its input
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(answer: Option<T>) -> Self {
+        answer.map_or(Answer::Unsolved, Into::into)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(String::from(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(24000u128), Answer::Number(24000));
+        assert_eq!(Answer::from(-3isize), Answer::Number(-3));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+        assert_eq!(Answer::from("CMZ"), Answer::Text(String::from("CMZ")));
+        assert_eq!(Answer::from(Some(7u32)), Answer::Number(7));
+        assert_eq!(Answer::from(None::<u32>), Answer::Unsolved);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::Number(13140).to_string(), "13140");
+        assert_eq!(Answer::Text(String::from("MCD")).to_string(), "MCD");
+        assert_eq!(
+            Answer::Image(vec![String::from("#.#"), String::from(".#.")]).to_string(),
+            "\n#.#\n.#."
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use std::{convert::TryFrom, fmt, fs, io, path::Path};
+
+use toml::{Table, Value};
+
+use crate::Answer;
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail(Answer),
+    Missing,
+}
+
+impl Verdict {
+    // Compared as displayed, so numbers too large for TOML integers still match
+    pub fn check(expected: Option<&Answer>, actual: &Answer) -> Verdict {
+        match expected {
+            Some(expected) if expected.to_string() == actual.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.clone()),
+            None => Verdict::Missing,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(_) => write!(f, "FAIL"),
+            Verdict::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+// Recorded answers on the real inputs, stored as `[dayNN]` tables with `partN` keys
+#[derive(Debug, Default)]
+pub struct Answers {
+    table: Table,
+}
+
+impl Answers {
+    pub fn parse(contents: &str) -> Result<Answers, toml::de::Error> {
+        Ok(Answers {
+            table: contents.parse()?,
+        })
+    }
+
+    pub fn load(path: &Path) -> io::Result<Answers> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Answers::parse(&contents)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<Answer> {
+        let value = self
+            .table
+            .get(&day_key(day))?
+            .as_table()?
+            .get(&part_key(part))?;
+        match value {
+            Value::Integer(n) => Some(Answer::Number(*n as i128)),
+            Value::String(s) => Some(Answer::Text(s.clone())),
+            Value::Array(rows) => Some(Answer::Image(
+                rows.iter()
+                    .map(|row| row.as_str().map(String::from))
+                    .collect::<Option<Vec<_>>>()?,
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, answer: &Answer) {
+        let value = match answer {
+            Answer::Number(n) => match i64::try_from(*n) {
+                Ok(n) => Value::Integer(n),
+                Err(_) => Value::String(n.to_string()),
+            },
+            Answer::Text(s) => Value::String(s.clone()),
+            Answer::Image(rows) => Value::Array(rows.iter().cloned().map(Value::String).collect()),
+            Answer::Unsolved => return,
+        };
+        if let Value::Table(day_table) = self
+            .table
+            .entry(day_key(day))
+            .or_insert_with(|| Value::Table(Table::new()))
+        {
+            day_table.insert(part_key(part), value);
+        }
+    }
+
+    pub fn verify(&self, day: u32, part: u32, actual: &Answer) -> Verdict {
+        Verdict::check(self.get(day, part).as_ref(), actual)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.table)
+    }
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{:02}", day)
+}
+
+fn part_key(part: u32) -> String {
+    format!("part{}", part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_ANSWERS: &str =
+        "[day01]\npart1 = 24000\npart2 = 45000\n\n[day05]\npart1 = \"CMZ\"\n";
+
+    #[test]
+    fn test_get() {
+        let answers = Answers::parse(TEST_ANSWERS).unwrap();
+        assert_eq!(answers.get(1, 1), Some(Answer::Number(24000)));
+        assert_eq!(answers.get(5, 1), Some(Answer::Text(String::from("CMZ"))));
+        assert_eq!(answers.get(5, 2), None);
+        assert_eq!(answers.get(2, 1), None);
+    }
+
+    #[test]
+    fn test_verify() {
+        let answers = Answers::parse(TEST_ANSWERS).unwrap();
+        assert_eq!(answers.verify(1, 1, &Answer::Number(24000)), Verdict::Pass);
+        assert_eq!(
+            answers.verify(1, 2, &Answer::Number(1)),
+            Verdict::Fail(Answer::Number(45000))
+        );
+        assert_eq!(answers.verify(5, 2, &Answer::from("MCD")), Verdict::Missing);
+    }
+
+    #[test]
+    fn test_insert_round_trip() {
+        let mut answers = Answers::default();
+        let image = Answer::Image(vec![String::from("#.#"), String::from(".#.")]);
+        answers.insert(10, 2, &image);
+        answers.insert(11, 2, &Answer::Number(i128::MAX));
+        answers.insert(1, 1, &Answer::Number(24000));
+
+        let reloaded = Answers::parse(&answers.to_string()).unwrap();
+        assert_eq!(reloaded.get(10, 2), Some(image));
+        assert_eq!(reloaded.get(1, 1), Some(Answer::Number(24000)));
+        assert_eq!(
+            reloaded.verify(11, 2, &Answer::Number(i128::MAX)),
+            Verdict::Pass
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{runner::part_fns, Day, Params, ParseError};
+
+// Stop conditions for the measured iterations, after warming up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Budget {
+    Iterations(usize),
+    Time(Duration),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchConfig {
+    pub warmup: usize,
+    pub budget: Budget,
+    // only used with a time budget, so very slow parts still get a distribution
+    pub min_samples: usize,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup: 3,
+            budget: Budget::Time(Duration::from_secs(1)),
+            min_samples: 5,
+        }
+    }
+}
+
+// All times in milliseconds, to be formatted with the runner's `fmt_time`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub p95: f64,
+    pub std_dev: f64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut ms = samples
+            .iter()
+            .map(|sample| sample.as_secs_f64() * 1000.0)
+            .collect::<Vec<f64>>();
+        ms.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let n = ms.len();
+        let mean = ms.iter().sum::<f64>() / n as f64;
+        let median = if n % 2 == 0 {
+            (ms[n / 2 - 1] + ms[n / 2]) / 2.0
+        } else {
+            ms[n / 2]
+        };
+        // nearest-rank percentile
+        let p95 = ms[((0.95 * n as f64).ceil() as usize).max(1) - 1];
+        let std_dev = if n > 1 {
+            (ms.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64).sqrt()
+        } else {
+            0.0
+        };
+
+        Some(Stats {
+            samples: n,
+            min: ms[0],
+            median,
+            mean,
+            p95,
+            std_dev,
+        })
+    }
+}
+
+pub fn measure<F: FnMut()>(config: &BenchConfig, mut f: F) -> Stats {
+    for _ in 0..config.warmup {
+        f();
+    }
+
+    let mut samples = vec![];
+    let bench_start = Instant::now();
+    loop {
+        let done = match config.budget {
+            Budget::Iterations(iterations) => samples.len() >= iterations.max(1),
+            Budget::Time(budget) => {
+                bench_start.elapsed() >= budget && samples.len() >= config.min_samples.max(1)
+            }
+        };
+        if done {
+            break;
+        }
+        let start = Instant::now();
+        f();
+        samples.push(start.elapsed());
+    }
+    Stats::from_samples(&samples).unwrap()
+}
+
+// Benchmarks parsing and every implemented part, labelled "parse", "1" and "2"
+pub fn bench_day(
+    day: &Day,
+    input: &str,
+    params: &Params,
+    config: &BenchConfig,
+) -> Result<Vec<(String, Stats)>, ParseError> {
+    // fail once up front rather than inside the measured loop
+    let parsed = (day.parse)(input)?;
+    let mut results = vec![(
+        String::from("parse"),
+        measure(config, || {
+            black_box((day.parse)(black_box(input))).ok();
+        }),
+    )];
+
+    part_fns(day).into_iter().for_each(|(part, part_fn)| {
+        results.push((
+            part.to_string(),
+            measure(config, || {
+                black_box(part_fn(black_box(&parsed), params));
+            }),
+        ))
+    });
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_from_samples() {
+        let samples = [4, 1, 3, 2, 5]
+            .iter()
+            .map(|ms| Duration::from_millis(*ms))
+            .collect::<Vec<_>>();
+        let stats = Stats::from_samples(&samples).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.p95, 5.0);
+        assert!((stats.std_dev - 2.5_f64.sqrt()).abs() < 1e-9);
+
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_measure_iterations() {
+        let mut calls = 0;
+        let config = BenchConfig {
+            warmup: 2,
+            budget: Budget::Iterations(10),
+            min_samples: 1,
+        };
+        let stats = measure(&config, || calls += 1);
+        assert_eq!(stats.samples, 10);
+        assert_eq!(calls, 12);
+    }
+}
@@ -1,50 +1,86 @@
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
-}
+use crate::{generate::Rng, parse_error::lines, Answer, Example, Params, ParseError, Solution};
+
+pub(crate) struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+
+    type Parsed = Vec<u128>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_elves(input)
+    }
+
+    fn part1(elves: &Self::Parsed, _params: &Params) -> Answer {
+        part1_impl(elves).into()
+    }
+
+    fn part2(elves: &Self::Parsed, _params: &Params) -> Answer {
+        part2_impl(elves).into()
+    }
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(24000).part2(45000)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
-fn parse_elves(input: &str) -> Vec<u128> {
+fn parse_elves(input: &str) -> Result<Vec<u128>, ParseError> {
     let mut elves: Vec<u128> = vec![];
     let mut current_elf = 0;
-    input.lines().enumerate().for_each(|(_, line)| {
-        if line.is_empty() {
+    for line in lines(input) {
+        if line.text.is_empty() {
             elves.push(current_elf);
             current_elf = 0;
         } else {
-            current_elf += line.parse::<u128>().unwrap();
+            current_elf += line.parse::<u128>(line.text)?;
         }
-    });
+    }
     elves.push(current_elf);
     elves.sort_by(|x, y| y.cmp(x));
-    elves
+    Ok(elves)
 }
 
-fn part1_impl(input: &str) -> u128 {
-    let elves = parse_elves(input);
+fn part1_impl(elves: &[u128]) -> u128 {
     *elves.first().unwrap()
 }
 
-fn part2_impl(input: &str) -> u128 {
-    let elves = parse_elves(input);
-    elves[0..3].into_iter().fold(0, |acc, el| acc + el)
+// With fewer than three elves, all of them
+fn part2_impl(elves: &[u128]) -> u128 {
+    elves.iter().take(3).sum()
 }
 
+// `size` elves, at least the three part 2 adds up, carrying one to six snacks each
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut elves = vec![];
+    for _ in 0..size.max(3) {
+        let snacks = (0..rng.between(1, 6))
+            .map(|_| rng.between(1000, 60000).to_string())
+            .collect::<Vec<_>>();
+        elves.push(snacks.join("\n"));
+    }
+    elves.join("\n\n")
+}
+
+static EXAMPLE_INPUT: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    static TEST_INPUT: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
-
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), 24000);
+        assert_eq!(part1_impl(&parse_elves(EXAMPLE_INPUT).unwrap()), 24000);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), 45000);
+        assert_eq!(part2_impl(&parse_elves(EXAMPLE_INPUT).unwrap()), 45000);
+        assert_eq!(part2_impl(&parse_elves("1000\n\n2000").unwrap()), 3000);
+        assert_eq!(part2_impl(&parse_elves("").unwrap()), 0);
     }
 }
@@ -1,57 +1,81 @@
-use itertools::Itertools;
-use std::char;
+use crate::{
+    generate::Rng,
+    parse_error::{lines, Line},
+    Answer, Example, Params, ParseError, Solution,
+};
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
-}
+pub(crate) struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    type Parsed = Vec<(Shape, Shape, Intention)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(lines: &Self::Parsed, _params: &Params) -> Answer {
+        part1_impl(lines).into()
+    }
+
+    fn part2(lines: &Self::Parsed, _params: &Params) -> Answer {
+        part2_impl(lines).into()
+    }
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(15).part2(12)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
+
 #[derive(Clone, Copy)]
-enum Shape {
+pub(crate) enum Shape {
     Rock = 1,
     Paper = 2,
     Scissors = 3,
 }
 
 #[derive(Clone, Copy)]
-enum Intention {
+pub(crate) enum Intention {
     Lose,
     Draw,
     Win,
 }
 
-fn transform_to_shape(c: Option<char>) -> Shape {
-    match c {
-        Some('A') | Some('X') => Shape::Rock,
-        Some('B') | Some('Y') => Shape::Paper,
-        Some('C') | Some('Z') => Shape::Scissors,
-        _ => panic!("Unknown shape"),
+fn transform_to_shape(line: &Line, part: &str) -> Result<Shape, ParseError> {
+    match part {
+        "A" | "X" => Ok(Shape::Rock),
+        "B" | "Y" => Ok(Shape::Paper),
+        "C" | "Z" => Ok(Shape::Scissors),
+        _ => Err(line.error(part, "unknown shape")),
     }
 }
 
-fn transform_to_intention(c: Option<char>) -> Intention {
-    match c {
-        Some('X') => Intention::Lose,
-        Some('Y') => Intention::Draw,
-        Some('Z') => Intention::Win,
-        _ => panic!("Unknown intention"),
+fn transform_to_intention(line: &Line, part: &str) -> Result<Intention, ParseError> {
+    match part {
+        "X" => Ok(Intention::Lose),
+        "Y" => Ok(Intention::Draw),
+        "Z" => Ok(Intention::Win),
+        _ => Err(line.error(part, "unknown intention")),
     }
 }
 
-fn parse_lines(input: &str) -> Vec<(Shape, Shape, Intention)> {
-    input
-        .lines()
-        .enumerate()
-        .map(|(_, line)| {
-            (
-                transform_to_shape(line.chars().nth(0)),
-                transform_to_shape(line.chars().nth(2)),
-                transform_to_intention(line.chars().nth(2)),
-            )
+fn parse_lines(input: &str) -> Result<Vec<(Shape, Shape, Intention)>, ParseError> {
+    lines(input)
+        .map(|line| {
+            let (opponent, player) = line.split_once(" ")?;
+            Ok((
+                transform_to_shape(&line, opponent)?,
+                transform_to_shape(&line, player)?,
+                transform_to_intention(&line, player)?,
+            ))
         })
-        .collect_vec()
+        .collect()
 }
 
 fn get_score_for_shape(shape: Shape) -> u128 {
@@ -102,37 +126,55 @@ fn get_score_fight(opponent: Shape, player: Shape) -> u128 {
     }
 }
 
-fn part1_impl(input: &str) -> u128 {
-    let lines = parse_lines(input);
-    lines.into_iter().fold(0, |score, (opponent, player, _)| {
+fn part1_impl(lines: &[(Shape, Shape, Intention)]) -> u128 {
+    lines.iter().fold(0, |score, &(opponent, player, _)| {
         score + get_score_fight(opponent, player) + get_score_for_shape(player)
     })
 }
 
-fn part2_impl(input: &str) -> u128 {
-    let lines = parse_lines(input);
-    lines
-        .into_iter()
-        .fold(0, |score, (opponent, _, intention)| {
-            score
-                + get_score_fight(opponent, get_shape_matching_intention(opponent, intention))
-                + get_score_for_shape(get_shape_matching_intention(opponent, intention))
+fn part2_impl(lines: &[(Shape, Shape, Intention)]) -> u128 {
+    lines.iter().fold(0, |score, &(opponent, _, intention)| {
+        score
+            + get_score_fight(opponent, get_shape_matching_intention(opponent, intention))
+            + get_score_for_shape(get_shape_matching_intention(opponent, intention))
+    })
+}
+
+// `size` rounds of the strategy guide
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            format!(
+                "{} {}",
+                rng.pick(&["A", "B", "C"]),
+                rng.pick(&["X", "Y", "Z"])
+            )
         })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
+static EXAMPLE_INPUT: &str = "A Y\nB X\nC Z";
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    static TEST_INPUT: &str = "A Y\nB X\nC Z";
-
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), 15);
+        assert_eq!(part1_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 15);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), 12);
+        assert_eq!(part2_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 12);
+    }
+
+    #[test]
+    fn test_parse_lines_error() {
+        let error = parse_lines("A Y\nB Q").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "Q");
+        assert_eq!(error.message, "unknown shape");
     }
 }
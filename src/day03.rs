@@ -1,23 +1,57 @@
 use itertools::Itertools;
 use std::char;
 
+use crate::{generate::Rng, parse_error::lines, Answer, Example, Params, ParseError, Solution};
+
 // (first half, second half, all)
-type Rucksack<'a> = (&'a str, &'a str, &'a str);
+pub(crate) type Rucksack = (String, String, String);
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
-}
+pub(crate) struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    type Parsed = Vec<Rucksack>;
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(rucksacks: &Self::Parsed, _params: &Params) -> Answer {
+        part1_impl(rucksacks).into()
+    }
+
+    fn part2(rucksacks: &Self::Parsed, _params: &Params) -> Answer {
+        part2_impl(rucksacks).into()
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(157).part2(70)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
-fn parse_lines(input: &str) -> Vec<Rucksack> {
-    input
-        .lines()
-        .enumerate()
-        .map(|(_, line)| (&line[..(line.len() / 2)], &line[(line.len() / 2)..], line))
-        .collect_vec()
+fn parse_lines(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    lines(input)
+        .map(|line| {
+            let text = line.text;
+            if let Some((index, c)) = text.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                return Err(line.error(&text[index..index + c.len_utf8()], "expected a letter"));
+            }
+            if text.len() % 2 != 0 {
+                return Err(line.error(text, "compartments differ in size"));
+            }
+            Ok((
+                String::from(&text[..(text.len() / 2)]),
+                String::from(&text[(text.len() / 2)..]),
+                String::from(text),
+            ))
+        })
+        .collect()
 }
 
 fn transform_to_priority(c: char) -> u32 {
@@ -29,9 +63,9 @@ fn transform_to_priority(c: char) -> u32 {
     }
 }
 
-fn find_duplicates_in_rucksack(rucksacks: &Vec<Rucksack>) -> Vec<u32> {
+fn find_duplicates_in_rucksack(rucksacks: &[Rucksack]) -> Vec<u32> {
     rucksacks
-        .into_iter()
+        .iter()
         .map(
             |rucksack| match rucksack.0.chars().find(|x| rucksack.1.chars().contains(x)) {
                 Some(x) => transform_to_priority(x),
@@ -41,11 +75,11 @@ fn find_duplicates_in_rucksack(rucksacks: &Vec<Rucksack>) -> Vec<u32> {
         .collect_vec()
 }
 
-fn find_three_elves_groups(rucksacks: &Vec<Rucksack>) -> Vec<u32> {
+fn find_three_elves_groups(rucksacks: &[Rucksack]) -> Vec<u32> {
     rucksacks
         .chunks(3)
         .map(|a| match a {
-            &[r1, r2, r3] => {
+            [r1, r2, r3] => {
                 match r1
                     .2
                     .chars()
@@ -60,34 +94,59 @@ fn find_three_elves_groups(rucksacks: &Vec<Rucksack>) -> Vec<u32> {
         .collect_vec()
 }
 
-fn part1_impl(input: &str) -> u32 {
-    let rucksacks = parse_lines(input);
-    find_duplicates_in_rucksack(&rucksacks)
+fn part1_impl(rucksacks: &[Rucksack]) -> u32 {
+    find_duplicates_in_rucksack(rucksacks)
         .into_iter()
         .sum::<u32>()
 }
 
-fn part2_impl(input: &str) -> u32 {
-    let rucksacks = parse_lines(input);
-    find_three_elves_groups(&rucksacks)
-        .into_iter()
-        .sum::<u32>()
+fn part2_impl(rucksacks: &[Rucksack]) -> u32 {
+    find_three_elves_groups(rucksacks).into_iter().sum::<u32>()
 }
 
+// `size` rucksacks, rounded up to whole groups of three. Only the badge is in all rucksacks of
+// a group and only one item type is in both compartments of a rucksack.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut rucksacks = vec![];
+    for _ in 0..size.max(1).div_ceil(3) {
+        let mut items = ('a'..='z').chain('A'..='Z').collect_vec();
+        rng.shuffle(&mut items);
+        let badge = items[0];
+        // every rucksack gets 17 item types of its own: one for both compartments and eight
+        // for each
+        for own in items[1..].chunks(17) {
+            let half = rng.between(3, 12) as usize;
+            let mut first = (0..half).map(|_| *rng.pick(&own[1..9])).collect_vec();
+            let mut second = (0..half).map(|_| *rng.pick(&own[9..])).collect_vec();
+            first[0] = own[0];
+            second[0] = own[0];
+            if rng.chance(50) {
+                first[1] = badge;
+            } else {
+                second[1] = badge;
+            }
+            rng.shuffle(&mut first);
+            rng.shuffle(&mut second);
+            rucksacks.push(first.into_iter().chain(second).collect::<String>());
+        }
+    }
+    rucksacks.join("\n")
+}
+
+static EXAMPLE_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    static TEST_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
-
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), 157);
+        assert_eq!(part1_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 157);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), 70);
+        assert_eq!(part2_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 70);
     }
 
     #[test]
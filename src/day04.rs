@@ -1,33 +1,76 @@
-use itertools::Itertools;
+use std::str::FromStr;
 
+use crate::{
+    generate::Rng,
+    parse_error::{lines, Line},
+    Answer, Example, Params, ParseError, Solution,
+};
+
+// The inclusive range of sections an elf is assigned, written like `2-4`
 #[derive(Debug, Copy, Clone, PartialEq)]
-struct Assignment {
-    from: u64,
-    to: u64,
+pub struct Assignment {
+    pub from: u64,
+    pub to: u64,
 }
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
-}
+impl FromStr for Assignment {
+    type Err = ParseError;
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+    fn from_str(s: &str) -> Result<Assignment, ParseError> {
+        parse_into_assignment(&Line::new(0, s), s)
+    }
 }
 
-fn parse_into_assignment(input: &str) -> Assignment {
-    Assignment {
-        from: input.split_once('-').unwrap().0.parse::<u64>().unwrap(),
-        to: input.split_once('-').unwrap().1.parse::<u64>().unwrap(),
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    type Parsed = Vec<(Assignment, Assignment)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
     }
+
+    fn part1(assignments: &Self::Parsed, _params: &Params) -> Answer {
+        solve_part1(assignments).into()
+    }
+
+    fn part2(assignments: &Self::Parsed, _params: &Params) -> Answer {
+        solve_part2(assignments).into()
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(2).part2(4)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
+}
+
+fn parse_into_assignment(line: &Line, input: &str) -> Result<Assignment, ParseError> {
+    let (from, to) = input
+        .split_once('-')
+        .ok_or_else(|| line.error(input, "expected a range like '2-4'"))?;
+    Ok(Assignment {
+        from: line.parse(from)?,
+        to: line.parse(to)?,
+    })
 }
 
-fn parse_lines(input: &str) -> Vec<(Assignment, Assignment)> {
-    input
-        .lines()
-        .enumerate()
-        .map(|(_, line)| line.split_once(',').unwrap())
-        .map(|(first, second)| (parse_into_assignment(first), parse_into_assignment(second)))
-        .collect_vec()
+// One pair of assignments per line, like `2-4,6-8`
+pub fn parse_lines(input: &str) -> Result<Vec<(Assignment, Assignment)>, ParseError> {
+    lines(input)
+        .map(|line| {
+            let (first, second) = line.split_once(",")?;
+            Ok((
+                parse_into_assignment(&line, first)?,
+                parse_into_assignment(&line, second)?,
+            ))
+        })
+        .collect()
 }
 
 fn assignment_contains_assignment((assignment1, assignment2): (Assignment, Assignment)) -> bool {
@@ -37,128 +80,112 @@ fn assignment_contains_assignment((assignment1, assignment2): (Assignment, Assig
 
 fn assignment_overlaps_assignment((assignment1, assignment2): (Assignment, Assignment)) -> bool {
     assignment_contains_assignment((assignment1, assignment2))
-        || (assignment1.to >= assignment2.from && assignment1.to <= assignment2.from)
-        || (assignment2.to >= assignment1.from && assignment2.to <= assignment1.from)
+        || assignment1.to == assignment2.from
+        || assignment2.to == assignment1.from
         || (assignment1.from >= assignment2.from && assignment1.from <= assignment2.to)
         || (assignment2.from >= assignment1.from && assignment2.from <= assignment1.to)
 }
 
-fn part1_impl(input: &str) -> usize {
-    let assignments = parse_lines(input);
-    *assignments
-        .into_iter()
-        .counts_by(assignment_contains_assignment)
-        .get(&true)
-        .unwrap()
+// Pairs where one assignment contains the other
+pub fn solve_part1(assignments: &[(Assignment, Assignment)]) -> usize {
+    assignments
+        .iter()
+        .copied()
+        .filter(|&pair| assignment_contains_assignment(pair))
+        .count()
 }
 
-fn part2_impl(input: &str) -> usize {
-    let assignments = parse_lines(input);
-    *assignments
-        .into_iter()
-        .counts_by(assignment_overlaps_assignment)
-        .get(&true)
-        .unwrap()
+// Pairs whose assignments overlap at all
+pub fn solve_part2(assignments: &[(Assignment, Assignment)]) -> usize {
+    assignments
+        .iter()
+        .copied()
+        .filter(|&pair| assignment_overlaps_assignment(pair))
+        .count()
 }
 
+// `size` pairs of section assignments
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut assignment = || {
+        let from = rng.between(1, 99);
+        format!("{}-{}", from, rng.between(from, 99))
+    };
+    (0..size.max(1))
+        .map(|_| format!("{},{}", assignment(), assignment()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+static EXAMPLE_INPUT: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use proptest::prelude::*;
 
-    static TEST_INPUT: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+    use super::*;
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), 2);
+        assert_eq!(solve_part1(&parse_lines(EXAMPLE_INPUT).unwrap()), 2);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), 4);
+        assert_eq!(solve_part2(&parse_lines(EXAMPLE_INPUT).unwrap()), 4);
     }
 
     #[test]
     fn test_assignment_contains_assignment() {
-        assert_eq!(
-            assignment_contains_assignment((
-                Assignment { from: 2, to: 8 },
-                Assignment { from: 3, to: 7 }
-            )),
-            true
-        );
-        assert_eq!(
-            assignment_contains_assignment((
-                Assignment { from: 6, to: 6 },
-                Assignment { from: 4, to: 6 }
-            )),
-            true
-        );
-        assert_eq!(
-            assignment_contains_assignment((
-                Assignment { from: 2, to: 6 },
-                Assignment { from: 4, to: 8 }
-            )),
-            false
-        );
+        assert!(assignment_contains_assignment((
+            Assignment { from: 2, to: 8 },
+            Assignment { from: 3, to: 7 }
+        )));
+        assert!(assignment_contains_assignment((
+            Assignment { from: 6, to: 6 },
+            Assignment { from: 4, to: 6 }
+        )));
+        assert!(!assignment_contains_assignment((
+            Assignment { from: 2, to: 6 },
+            Assignment { from: 4, to: 8 }
+        )));
     }
 
     #[test]
     fn test_assignment_overlaps_assignment() {
-        assert_eq!(
-            assignment_overlaps_assignment((
-                Assignment { from: 2, to: 4 },
-                Assignment { from: 6, to: 8 }
-            )),
-            false
-        );
-        assert_eq!(
-            assignment_overlaps_assignment((
-                Assignment { from: 2, to: 3 },
-                Assignment { from: 4, to: 5 }
-            )),
-            false
-        );
-        assert_eq!(
-            assignment_overlaps_assignment((
-                Assignment { from: 5, to: 7 },
-                Assignment { from: 7, to: 9 }
-            )),
-            true
-        );
-        assert_eq!(
-            assignment_overlaps_assignment((
-                Assignment { from: 2, to: 8 },
-                Assignment { from: 3, to: 7 }
-            )),
-            true
-        );
-        assert_eq!(
-            assignment_overlaps_assignment((
-                Assignment { from: 6, to: 6 },
-                Assignment { from: 4, to: 6 }
-            )),
-            true
-        );
-        assert_eq!(
-            assignment_overlaps_assignment((
-                Assignment { from: 2, to: 6 },
-                Assignment { from: 4, to: 8 }
-            )),
-            true
-        );
-        assert_eq!(
-            assignment_overlaps_assignment((
-                Assignment { from: 94, to: 96 },
-                Assignment { from: 5, to: 86 }
-            )),
-            false
-        );
+        assert!(!assignment_overlaps_assignment((
+            Assignment { from: 2, to: 4 },
+            Assignment { from: 6, to: 8 }
+        )));
+        assert!(!assignment_overlaps_assignment((
+            Assignment { from: 2, to: 3 },
+            Assignment { from: 4, to: 5 }
+        )));
+        assert!(assignment_overlaps_assignment((
+            Assignment { from: 5, to: 7 },
+            Assignment { from: 7, to: 9 }
+        )));
+        assert!(assignment_overlaps_assignment((
+            Assignment { from: 2, to: 8 },
+            Assignment { from: 3, to: 7 }
+        )));
+        assert!(assignment_overlaps_assignment((
+            Assignment { from: 6, to: 6 },
+            Assignment { from: 4, to: 6 }
+        )));
+        assert!(assignment_overlaps_assignment((
+            Assignment { from: 2, to: 6 },
+            Assignment { from: 4, to: 8 }
+        )));
+        assert!(!assignment_overlaps_assignment((
+            Assignment { from: 94, to: 96 },
+            Assignment { from: 5, to: 86 }
+        )));
     }
 
     #[test]
     fn test_parse_lines() {
         assert_eq!(
-            parse_lines(TEST_INPUT),
+            parse_lines(EXAMPLE_INPUT).unwrap(),
             vec![
                 (Assignment { from: 2, to: 4 }, Assignment { from: 6, to: 8 }),
                 (Assignment { from: 2, to: 3 }, Assignment { from: 4, to: 5 }),
@@ -170,8 +197,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_str() {
+        assert_eq!("2-4".parse(), Ok(Assignment { from: 2, to: 4 }));
+        assert_eq!("2-x".parse::<Assignment>().unwrap_err().column, 3);
+        assert_eq!(solve_part1(&[]), 0);
+    }
+
     #[test]
     fn test_parse_into_assignment() {
-        assert_eq!(parse_into_assignment("2-4"), Assignment { from: 2, to: 4 });
+        let line = Line::new(0, "2-4");
+        assert_eq!(
+            parse_into_assignment(&line, line.text),
+            Ok(Assignment { from: 2, to: 4 })
+        );
+        let line = Line::new(0, "2-4,6+8");
+        let error = parse_into_assignment(&line, &line.text[4..]).unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (5, "6+8"));
+    }
+
+    fn assignment() -> impl Strategy<Value = Assignment> {
+        (0..100_u64, 0..100_u64).prop_map(|(from, length)| Assignment {
+            from,
+            to: from + length,
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn overlaps_is_symmetric(first in assignment(), second in assignment()) {
+            let overlaps = assignment_overlaps_assignment((first, second));
+            prop_assert_eq!(assignment_overlaps_assignment((second, first)), overlaps);
+            prop_assert_eq!(overlaps, first.from <= second.to && second.from <= first.to);
+            if assignment_contains_assignment((first, second)) {
+                prop_assert!(overlaps);
+            }
+        }
+
+        #[test]
+        fn parse_round_trips(first in assignment(), second in assignment()) {
+            let line = format!("{}-{},{}-{}", first.from, first.to, second.from, second.to);
+            prop_assert_eq!(parse_lines(&line), Ok(vec![(first, second)]));
+        }
     }
 }
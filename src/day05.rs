@@ -1,54 +1,152 @@
 use itertools::Itertools;
 use regex::Regex;
-use std::collections::{HashMap, LinkedList};
+use std::{
+    collections::{HashMap, LinkedList},
+    str::FromStr,
+};
 
-type Stack = LinkedList<char>;
-type CrateStacks = HashMap<usize, Stack>;
+use crate::{
+    generate::Rng,
+    parse_error::{lines, Line},
+    Answer, Example, Params, ParseError, Solution,
+};
 
+// Crates from top to bottom
+pub type Stack = LinkedList<char>;
+// Stacks by their number, starting at 1
+pub type CrateStacks = HashMap<usize, Stack>;
+
+// A line like `move 1 from 2 to 1`
 #[derive(Debug, Copy, Clone, PartialEq)]
-struct Move {
-    amount: usize,
-    from: usize,
-    to: usize,
+pub struct Move {
+    pub amount: usize,
+    pub from: usize,
+    pub to: usize,
 }
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
+impl FromStr for Move {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Move, ParseError> {
+        parse_move(&Line::new(0, s))
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+    // crates are placed by their column
+    const KEEP_INDENT: bool = true;
+
+    type Parsed = (CrateStacks, Vec<Move>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(stacks_and_moves: &Self::Parsed, _params: &Params) -> Answer {
+        solve_part1(stacks_and_moves).into()
+    }
+
+    fn part2(stacks_and_moves: &Self::Parsed, _params: &Params) -> Answer {
+        solve_part2(stacks_and_moves).into()
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1("CMZ").part2("MCD")]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+fn parse_move(line: &Line) -> Result<Move, ParseError> {
+    match line.text.split(' ').collect_vec().as_slice() {
+        ["move", amount, "from", from, "to", to] => Ok(Move {
+            amount: line.parse(amount)?,
+            from: line.parse(from)?,
+            to: line.parse(to)?,
+        }),
+        _ => Err(line.error(
+            line.text,
+            "expected 'move <amount> from <stack> to <stack>'",
+        )),
+    }
 }
 
-fn parse_lines(input: &str) -> (CrateStacks, Vec<Move>) {
+// The drawing of the stacks followed by the moves
+pub fn parse_lines(input: &str) -> Result<(CrateStacks, Vec<Move>), ParseError> {
     let mut crate_stacks = CrateStacks::default();
     let mut moves = vec![];
+    let mut move_lines = vec![];
     let crates_regex = Regex::new(r"(\[[A-Z]\])+").unwrap();
-    input.lines().enumerate().for_each(|(_, line)| {
-        if line.starts_with("move") {
-            let mut parts = line.split(" ");
-            moves.push(Move {
-                amount: parts.nth(1).unwrap().parse().unwrap(),
-                from: parts.nth(1).unwrap().parse().unwrap(),
-                to: parts.nth(1).unwrap().parse().unwrap(),
-            });
-        } else if crates_regex.is_match(line) {
-            line.chars().enumerate().for_each(|(index, c)| {
+    for line in lines(input) {
+        if line.text.starts_with("move") {
+            moves.push(parse_move(&line)?);
+            move_lines.push(line);
+        } else if crates_regex.is_match(line.text) {
+            for (index, c) in line.text.char_indices() {
                 if c != ' ' && c != '[' && c != ']' {
+                    if index % 4 != 1 {
+                        return Err(line.error(
+                            &line.text[index..index + c.len_utf8()],
+                            "crate is not aligned to a stack",
+                        ));
+                    }
                     crate_stacks
                         .entry(((index - 1) / 4) + 1)
                         .or_insert(Stack::default())
                         .push_back(c)
                 }
-            });
+            }
         }
-    });
-    (crate_stacks, moves)
+    }
+
+    // stacks left of the last one may start out empty
+    let count = crate_stacks.keys().copied().max().unwrap_or(0);
+    for stack in 1..=count {
+        crate_stacks.entry(stack).or_default();
+    }
+    let mut heights = (1..=count)
+        .map(|stack| crate_stacks[&stack].len())
+        .collect_vec();
+    for (line, &m) in move_lines.iter().zip(&moves) {
+        check_move(line, m, &mut heights)?;
+    }
+    Ok((crate_stacks, moves))
+}
+
+// Checks that `m` moves between existing stacks and no more crates than `from` holds,
+// `heights` counting the crates of each stack after the moves before it
+fn check_move(line: &Line, m: Move, heights: &mut [usize]) -> Result<(), ParseError> {
+    let words = line.text.split(' ').collect_vec();
+    for (stack, word) in [(m.from, words[3]), (m.to, words[5])] {
+        if !(1..=heights.len()).contains(&stack) {
+            return Err(line.error(
+                word,
+                &format!("expected a stack from 1 to {}", heights.len()),
+            ));
+        }
+    }
+    let available = heights[m.from - 1];
+    if m.amount > available {
+        return Err(line.error(
+            words[1],
+            &format!("stack {} only holds {} crates", m.from, available),
+        ));
+    }
+    heights[m.from - 1] -= m.amount;
+    heights[m.to - 1] += m.amount;
+    Ok(())
 }
 
-fn part1_impl(input: &str) -> String {
-    let (mut crate_stacks, moves) = parse_lines(input);
-    moves.into_iter().for_each(|m| {
+// Top crates after moving them one at a time
+pub fn solve_part1((crate_stacks, moves): &(CrateStacks, Vec<Move>)) -> String {
+    let mut crate_stacks = crate_stacks.clone();
+    moves.iter().for_each(|m| {
         let mut pushed_stack = Stack::default();
         crate_stacks.entry(m.from).and_modify(|stack| {
             for _ in 0..m.amount {
@@ -61,18 +159,18 @@ fn part1_impl(input: &str) -> String {
             crate_stacks.entry(m.to).and_modify(|s| s.push_front(c));
         }
     });
-    crate_stacks.into_iter().sorted().fold(
-        String::default(),
-        |mut acc: String, (_, mut stack): (_, Stack)| {
-            acc.push(stack.pop_front().unwrap());
-            acc
-        },
-    )
+    // an empty stack has no crate on top to show
+    crate_stacks
+        .into_iter()
+        .sorted()
+        .filter_map(|(_, stack)| stack.front().copied())
+        .collect()
 }
 
-fn part2_impl(input: &str) -> String {
-    let (mut crate_stacks, moves) = parse_lines(input);
-    moves.into_iter().for_each(|m| {
+// Top crates after moving them several at once
+pub fn solve_part2((crate_stacks, moves): &(CrateStacks, Vec<Move>)) -> String {
+    let mut crate_stacks = crate_stacks.clone();
+    moves.iter().for_each(|m| {
         let mut pushed_stack = Stack::default();
         crate_stacks.entry(m.from).and_modify(|stack| {
             for _ in 0..m.amount {
@@ -85,41 +183,82 @@ fn part2_impl(input: &str) -> String {
             crate_stacks.entry(m.to).and_modify(|s| s.push_front(c));
         }
     });
-    crate_stacks.into_iter().sorted().fold(
-        String::default(),
-        |mut acc: String, (_, mut stack): (_, Stack)| {
-            acc.push(stack.pop_front().unwrap());
-            acc
-        },
-    )
+    // an empty stack has no crate on top to show
+    crate_stacks
+        .into_iter()
+        .sorted()
+        .filter_map(|(_, stack)| stack.front().copied())
+        .collect()
 }
 
+// Nine stacks of up to eight crates and `size` moves, none of which empties a stack so every
+// stack has a crate on top at the end
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut heights = (0..9)
+        .map(|stack| rng.between(if stack == 0 { 2 } else { 1 }, 8) as usize)
+        .collect_vec();
+    let tallest = *heights.iter().max().unwrap();
+    let mut lines = (0..tallest)
+        .rev()
+        .map(|level| {
+            heights
+                .iter()
+                .map(|&height| {
+                    if level < height {
+                        format!("[{}]", (b'A' + rng.below(26) as u8) as char)
+                    } else {
+                        String::from("   ")
+                    }
+                })
+                .join(" ")
+        })
+        .collect_vec();
+    lines.push((1..=9).map(|stack| format!(" {} ", stack)).join(" "));
+    lines.push(String::new());
+
+    for _ in 0..size {
+        let from = *rng.pick(&(0..9).filter(|&stack| heights[stack] > 1).collect_vec());
+        let to = (from + rng.between(1, 8) as usize) % 9;
+        let amount = rng.between(1, heights[from] as i64 - 1) as usize;
+        heights[from] -= amount;
+        heights[to] += amount;
+        lines.push(format!("move {} from {} to {}", amount, from + 1, to + 1));
+    }
+    lines.join("\n")
+}
+
+static EXAMPLE_INPUT: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2";
+
 #[cfg(test)]
 mod tests {
     use std::vec;
 
     use super::*;
 
-    static TEST_INPUT: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2";
-
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), String::from("CMZ"));
+        assert_eq!(
+            solve_part1(&parse_lines(EXAMPLE_INPUT).unwrap()),
+            String::from("CMZ")
+        );
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), String::from("MCD"));
+        assert_eq!(
+            solve_part2(&parse_lines(EXAMPLE_INPUT).unwrap()),
+            String::from("MCD")
+        );
     }
 
     #[test]
     fn test_parse_lines() {
         assert_eq!(
-            parse_lines(TEST_INPUT),
+            parse_lines(EXAMPLE_INPUT).unwrap(),
             (
                 CrateStacks::from([
-                    (1, Stack::from(['Z', 'N'])),
-                    (2, Stack::from(['M', 'C', 'D'])),
+                    (1, Stack::from(['N', 'Z'])),
+                    (2, Stack::from(['D', 'C', 'M'])),
                     (3, Stack::from(['P']))
                 ]),
                 vec![
@@ -147,4 +286,50 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "move 3 from 1 to 3".parse(),
+            Ok(Move {
+                amount: 3,
+                from: 1,
+                to: 3
+            })
+        );
+        assert!("move 3 from 1".parse::<Move>().is_err());
+    }
+
+    #[test]
+    fn test_parse_lines_errors() {
+        let input = |moves| {
+            format!(
+                "{}{}",
+                &EXAMPLE_INPUT[..EXAMPLE_INPUT.find("move").unwrap()],
+                moves
+            )
+        };
+        let error = parse_lines(&input("move 1 from 9 to 1")).unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (13, "9"));
+        assert_eq!(error.message, "expected a stack from 1 to 3");
+        let error = parse_lines(&input("move 1 from 1 to 0")).unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (18, "0"));
+        // the second move empties stack 3 again
+        let error = parse_lines(&input(
+            "move 1 from 1 to 3\nmove 2 from 3 to 1\nmove 1 from 3 to 2",
+        ))
+        .unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (8, "1"));
+        assert_eq!(error.message, "stack 3 only holds 0 crates");
+        // an empty stack in the drawing can still be moved onto
+        let (stacks, _) = parse_lines("[A]     [B]\n 1   2   3 \n\nmove 1 from 1 to 2").unwrap();
+        assert_eq!(stacks[&2], Stack::new());
+        assert_eq!(
+            solve_part1(
+                &parse_lines("[A]     [B]\n 1   2   3 \n\nmove 1 from 3 to 2\nmove 1 from 2 to 3")
+                    .unwrap()
+            ),
+            "AB"
+        );
+    }
 }
@@ -1,29 +1,110 @@
 use itertools::Itertools;
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
-}
+use crate::{generate::Rng, parse_error::lines, Answer, Example, Params, ParseError, Solution};
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
-}
+pub(crate) struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+
+    type Parsed = String;
 
-fn part1_impl(input: &str) -> usize {
-    for i in 0..(input.len() - 3) {
-        if input[i..i + 4].chars().all_unique() {
-            return i + 4;
-        }
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_signal(input)
+    }
+
+    fn part1(signal: &Self::Parsed, _params: &Params) -> Answer {
+        part1_impl(signal).into()
+    }
+
+    fn part2(signal: &Self::Parsed, _params: &Params) -> Answer {
+        part2_impl(signal).into()
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![
+            Example::new("mjqjpqmgbljsphdztnvjfqwrcgsmlb")
+                .part1(7)
+                .part2(19),
+            Example::new("bvwbjplbgvbhsrlpgdmjqwftvncz")
+                .part1(5)
+                .part2(23),
+            Example::new("nppdvjthqldpwncqszvftbrmjlhg")
+                .part1(6)
+                .part2(23),
+            Example::new("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")
+                .part1(10)
+                .part2(29),
+            Example::new("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")
+                .part1(11)
+                .part2(26),
+        ]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
     }
-    0
 }
 
-fn part2_impl(input: &str) -> usize {
-    for i in 0..(input.len() - 13) {
-        if input[i..i + 14].chars().all_unique() {
-            return i + 14;
-        }
+// Packets start after 4 different characters, messages after 14
+const PACKET_MARKER: usize = 4;
+const MESSAGE_MARKER: usize = 14;
+
+// A single line of lowercase letters, long enough to hold at least a start-of-packet marker
+fn parse_signal(input: &str) -> Result<String, ParseError> {
+    let signal = input.trim_end();
+    let mut lines = lines(signal);
+    let line = lines
+        .next()
+        .ok_or_else(|| ParseError::at_end_of(input, "expected a signal"))?;
+    if let Some(extra) = lines.next() {
+        return Err(extra.error(extra.text, "expected the signal on a single line"));
     }
-    0
+    if let Some((index, c)) = line
+        .text
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii_lowercase())
+    {
+        return Err(line.error(
+            &line.text[index..index + c.len_utf8()],
+            "expected a lowercase letter",
+        ));
+    }
+    if line.text.len() < PACKET_MARKER {
+        return Err(line.error(
+            line.end(),
+            &format!("expected at least {} letters", PACKET_MARKER),
+        ));
+    }
+    Ok(String::from(signal))
+}
+
+// How many characters come in up to the end of the first `length` different ones in a row,
+// `None` when there are none
+fn find_marker(signal: &str, length: usize) -> Option<usize> {
+    signal
+        .as_bytes()
+        .windows(length)
+        .position(|window| window.iter().all_unique())
+        .map(|start| start + length)
+}
+
+fn part1_impl(signal: &str) -> Option<usize> {
+    find_marker(signal, PACKET_MARKER)
+}
+
+fn part2_impl(signal: &str) -> Option<usize> {
+    find_marker(signal, MESSAGE_MARKER)
+}
+
+// A datastream of `size` characters whose only markers are at the very end: everything before
+// uses just three letters, the last fourteen are all different
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut letters = ('a'..='z').collect_vec();
+    rng.shuffle(&mut letters);
+    let filler = (0..size.saturating_sub(14)).map(|_| *rng.pick(&letters[..3]));
+    filler.chain(letters[3..17].iter().copied()).collect()
 }
 
 #[cfg(test)]
@@ -32,19 +113,30 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 7);
-        assert_eq!(part1_impl("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
-        assert_eq!(part1_impl("nppdvjthqldpwncqszvftbrmjlhg"), 6);
-        assert_eq!(part1_impl("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
-        assert_eq!(part1_impl("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
+        assert_eq!(part1_impl("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Some(7));
+        assert_eq!(part1_impl("bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(5));
+        assert_eq!(part1_impl("nppdvjthqldpwncqszvftbrmjlhg"), Some(6));
+        assert_eq!(part1_impl("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Some(10));
+        assert_eq!(part1_impl("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Some(11));
+        assert_eq!(part1_impl("aaaa"), None);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
-        assert_eq!(part2_impl("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
-        assert_eq!(part2_impl("nppdvjthqldpwncqszvftbrmjlhg"), 23);
-        assert_eq!(part2_impl("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
-        assert_eq!(part2_impl("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
+        assert_eq!(part2_impl("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), Some(19));
+        assert_eq!(part2_impl("bvwbjplbgvbhsrlpgdmjqwftvncz"), Some(23));
+        assert_eq!(part2_impl("nppdvjthqldpwncqszvftbrmjlhg"), Some(23));
+        assert_eq!(part2_impl("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), Some(29));
+        assert_eq!(part2_impl("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), Some(26));
+        assert_eq!(part2_impl("abcd"), None);
+    }
+
+    #[test]
+    fn test_parse_signal() {
+        assert_eq!(parse_signal("abcd\n"), Ok(String::from("abcd")));
+        assert_eq!(parse_signal("ab").unwrap_err().column, 3);
+        assert_eq!(parse_signal("abcd\nefgh").unwrap_err().line, 2);
+        assert_eq!(parse_signal("abCd").unwrap_err().text, "C");
+        assert!(parse_signal("").is_err());
     }
 }
@@ -1,31 +1,109 @@
-use std::{cell::RefCell, cmp, rc::Rc};
+use std::{
+    cell::RefCell,
+    cmp,
+    rc::{Rc, Weak},
+    str::FromStr,
+};
 
-use itertools::Itertools;
+use crate::{
+    generate::Rng,
+    parse_error::{lines, Line},
+    Answer, Example, Param, Params, ParseError, Solution,
+};
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-enum EntryType {
+pub enum EntryType {
     Directory,
     File,
 }
 
-#[derive(Debug, PartialEq, Clone)]
-struct Entry {
-    entry_type: EntryType,
-    name: String,
-    size: isize,
-    parent: Option<Rc<RefCell<Entry>>>,
-    children: Vec<Rc<RefCell<Entry>>>,
+// A file or directory, directories link to their parent and list their children once `ls`
+// ran in them. Sizes are only known for files. The parent link is weak so that the tree is
+// freed with its root and printing or comparing entries does not go round in circles.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub entry_type: EntryType,
+    pub name: String,
+    pub size: isize,
+    pub parent: Option<Weak<RefCell<Entry>>>,
+    pub children: Vec<Rc<RefCell<Entry>>>,
 }
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
+// Entries are equal when their subtrees are, wherever they hang
+impl PartialEq for Entry {
+    fn eq(&self, other: &Entry) -> bool {
+        self.entry_type == other.entry_type
+            && self.name == other.name
+            && self.size == other.size
+            && self.children == other.children
+    }
 }
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+// A line of `ls` output, like `dir a` or `14848514 b.txt`, without a parent
+impl FromStr for Entry {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Entry, ParseError> {
+        parse_entry(&Line::new(0, s))
+    }
 }
 
-fn parse_lines(input: &str) -> Rc<RefCell<Entry>> {
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+
+    const PARAMS: &'static [Param] = &[
+        Param::new("disk_size", 70000000).min(0),
+        Param::new("needed_space", 30000000).min(0),
+    ];
+
+    type Parsed = Rc<RefCell<Entry>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(tree: &Self::Parsed, _params: &Params) -> Answer {
+        solve_part1(tree).into()
+    }
+
+    fn part2(tree: &Self::Parsed, params: &Params) -> Answer {
+        solve_part2(tree, params.get("disk_size"), params.get("needed_space")).into()
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(95437).part2(24933642)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> Option<String> {
+        Some(generate_input(
+            rng,
+            size,
+            params.get("disk_size"),
+            params.get("needed_space"),
+        ))
+    }
+}
+
+fn parse_entry(line: &Line) -> Result<Entry, ParseError> {
+    let (size, name) = line.split_once(" ")?;
+    Ok(Entry {
+        entry_type: if size == "dir" {
+            EntryType::Directory
+        } else {
+            EntryType::File
+        },
+        name: String::from(name),
+        size: if size == "dir" { 0 } else { line.parse(size)? },
+        parent: None,
+        children: Vec::default(),
+    })
+}
+
+// The root directory of a terminal session of `cd` and `ls` commands
+pub fn parse_lines(input: &str) -> Result<Rc<RefCell<Entry>>, ParseError> {
     let directory_tree = Rc::new(RefCell::new(Entry {
         entry_type: EntryType::Directory,
         name: String::from("/"),
@@ -34,78 +112,53 @@ fn parse_lines(input: &str) -> Rc<RefCell<Entry>> {
         children: Vec::default(),
     }));
     let mut current_node = Rc::clone(&directory_tree);
-    let mut lines = input.lines().skip(1).peekable();
-    'main: loop {
-        match lines.next() {
-            Some(line) => {
-                if line.starts_with("$ ls") {
-                    let mut new_contents = Vec::default();
-                    loop {
-                        match lines.peek() {
-                            Some(&next_line) => {
-                                if next_line.starts_with("dir") {
-                                    let current_line = lines
-                                        .next()
-                                        .unwrap()
-                                        .split_whitespace()
-                                        .collect::<Vec<&str>>();
-
-                                    new_contents.push(Rc::new(RefCell::new(Entry {
-                                        entry_type: EntryType::Directory,
-                                        name: String::from(current_line[1]),
-                                        size: 0,
-                                        parent: Some(Rc::clone(&current_node)),
-                                        children: Vec::default(),
-                                    })));
-                                } else if next_line.chars().next().unwrap().is_numeric() {
-                                    let current_line = lines
-                                        .next()
-                                        .unwrap()
-                                        .split_whitespace()
-                                        .collect::<Vec<&str>>();
-                                    new_contents.push(Rc::new(RefCell::new(Entry {
-                                        entry_type: EntryType::File,
-                                        name: String::from(current_line[1]),
-                                        size: current_line[0].parse::<isize>().unwrap(),
-                                        parent: None,
-                                        children: Vec::default(),
-                                    })));
-                                } else {
-                                    break;
-                                }
-                            }
-                            _ => break,
-                        }
-                    }
-                    current_node.borrow_mut().children = new_contents;
-                } else if line.starts_with("$ cd") {
-                    let target_directory = line.split(" ").collect_vec()[2];
-                    let new_node = Rc::clone(&current_node);
-                    if target_directory == ".." {
-                        current_node = Rc::clone(new_node.borrow_mut().parent.as_ref().unwrap());
-                    } else {
-                        current_node = Rc::clone(
-                            new_node
-                                .borrow_mut()
-                                .children
-                                .iter()
-                                .find(|x| {
-                                    let y = x.borrow_mut();
-                                    y.name == target_directory
-                                        && y.entry_type == EntryType::Directory
-                                })
-                                .unwrap(),
-                        );
-                    }
-                } else {
-                    break;
+    let mut lines = lines(input).peekable();
+    // the session starts at the root, which the tree above already is
+    match lines.next() {
+        Some(line) if line.text == "$ cd /" => {}
+        Some(line) => return Err(line.error(line.text, "expected '$ cd /'")),
+        None => return Err(ParseError::at_end_of(input, "expected '$ cd /'")),
+    }
+    while let Some(line) = lines.next() {
+        if line.text.starts_with("$ ls") {
+            let mut new_contents = Vec::default();
+            while let Some(next_line) = lines.next_if(|next_line| !next_line.text.starts_with('$'))
+            {
+                let mut entry = parse_entry(&next_line)?;
+                if entry.entry_type == EntryType::Directory {
+                    entry.parent = Some(Rc::downgrade(&current_node));
                 }
+                new_contents.push(Rc::new(RefCell::new(entry)));
             }
-            _ => break 'main,
+            current_node.borrow_mut().children = new_contents;
+        } else if let Some(target_directory) = line.text.strip_prefix("$ cd ") {
+            let next_node = if target_directory == ".." {
+                current_node
+                    .borrow()
+                    .parent
+                    .as_ref()
+                    .and_then(Weak::upgrade)
+            } else if target_directory == "/" {
+                Some(Rc::clone(&directory_tree))
+            } else {
+                current_node
+                    .borrow()
+                    .children
+                    .iter()
+                    .find(|x| {
+                        let y = x.borrow();
+                        y.name == target_directory && y.entry_type == EntryType::Directory
+                    })
+                    .cloned()
+            };
+            current_node =
+                next_node.ok_or_else(|| line.error(target_directory, "no such directory"))?;
+        } else {
+            return Err(line.error(line.text, "expected '$ cd' or '$ ls'"));
         }
     }
 
-    directory_tree
+    Ok(directory_tree)
 }
 
 fn calculate_directory_size(directory: &Rc<RefCell<Entry>>) -> isize {
@@ -114,7 +167,7 @@ fn calculate_directory_size(directory: &Rc<RefCell<Entry>>) -> isize {
         let result = ref_directory.children.iter().fold(0, |acc, child| {
             let entry = (*child).as_ptr();
             match (*entry).entry_type {
-                EntryType::Directory => acc + calculate_directory_size(&child),
+                EntryType::Directory => acc + calculate_directory_size(child),
                 EntryType::File => acc + (*entry).size,
             }
         });
@@ -129,11 +182,11 @@ fn sum_directories(directory: &Rc<RefCell<Entry>>) -> isize {
             let entry = (*child).as_ptr();
             match (*entry).entry_type {
                 EntryType::Directory => {
-                    let r = calculate_directory_size(&child);
+                    let r = calculate_directory_size(child);
                     if r <= 100000 {
-                        acc + r + sum_directories(&child)
+                        acc + r + sum_directories(child)
                     } else {
-                        acc + sum_directories(&child)
+                        acc + sum_directories(child)
                     }
                 }
                 _ => acc,
@@ -143,18 +196,27 @@ fn sum_directories(directory: &Rc<RefCell<Entry>>) -> isize {
     }
 }
 
-fn remove_directory(directory: &Rc<RefCell<Entry>>, total_size: isize, smallest: isize) -> isize {
+// Smallest directory that frees up `needed_space` on a disk of `disk_size` once deleted
+fn remove_directory(
+    directory: &Rc<RefCell<Entry>>,
+    total_size: isize,
+    smallest: isize,
+    disk_size: isize,
+    needed_space: isize,
+) -> isize {
     let ref_directory = directory.as_ref().borrow_mut();
     unsafe {
         let result = ref_directory.children.iter().fold(smallest, |acc, child| {
             let entry = (*child).as_ptr();
             match (*entry).entry_type {
                 EntryType::Directory => {
-                    let r = calculate_directory_size(&child);
-                    if r <= acc && 70000000 - total_size + r >= 30000000 {
-                        cmp::min(r, remove_directory(&child, total_size, smallest))
+                    let r = calculate_directory_size(child);
+                    let smallest_below =
+                        remove_directory(child, total_size, smallest, disk_size, needed_space);
+                    if r <= acc && disk_size - total_size + r >= needed_space {
+                        cmp::min(r, smallest_below)
                     } else {
-                        cmp::min(acc, remove_directory(&child, total_size, smallest))
+                        cmp::min(acc, smallest_below)
                     }
                 }
                 _ => acc,
@@ -163,20 +225,79 @@ fn remove_directory(directory: &Rc<RefCell<Entry>>, total_size: isize, smallest:
         result
     }
 }
-fn part1_impl(input: &str) -> isize {
-    let tree = parse_lines(input);
-    sum_directories(&tree)
+// Total size of the directories of at most 100000
+pub fn solve_part1(tree: &Rc<RefCell<Entry>>) -> isize {
+    sum_directories(tree)
 }
 
-fn part2_impl(input: &str) -> isize {
-    let tree = parse_lines(input);
-    remove_directory(&tree, calculate_directory_size(&tree), 70000000)
+// Size of the smallest directory to delete to have `needed_space` free
+pub fn solve_part2(tree: &Rc<RefCell<Entry>>, disk_size: isize, needed_space: isize) -> isize {
+    remove_directory(
+        tree,
+        calculate_directory_size(tree),
+        disk_size,
+        disk_size,
+        needed_space,
+    )
+}
+
+// Lowercase letters spelling out `index` in base 26, so every directory gets its own name
+fn directory_name(mut index: usize) -> String {
+    let mut name = vec![];
+    loop {
+        name.push((b'a' + (index % 26) as u8) as char);
+        index /= 26;
+        if index == 0 {
+            return name.into_iter().rev().collect();
+        }
+    }
 }
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    static TEST_INPUT: &str = "$ cd /
+// A session listing and entering each of `size` directories below `/`, every directory holding
+// up to four files. The files add up to a bit more than fits while keeping `needed_space` free,
+// so part 2 has to delete something.
+fn generate_input(rng: &mut Rng, size: usize, disk_size: isize, needed_space: isize) -> String {
+    // index 0 is `/`
+    let mut subdirectories = vec![vec![]; size + 1];
+    for directory in 1..=size {
+        subdirectories[rng.below(directory)].push(directory);
+    }
+    let file_counts = (0..=size).map(|_| rng.below(5)).collect::<Vec<_>>();
+    let target = (disk_size - needed_space).max(0) as i64 + rng.between(1, 5_000_000);
+    let average_size = (target / file_counts.iter().sum::<usize>().max(1) as i64).max(1);
+
+    let mut lines = vec![String::from("$ cd /")];
+    // directories still to list, `None` to go back up
+    let mut pending = vec![Some(0)];
+    while let Some(next) = pending.pop() {
+        let directory = match next {
+            Some(directory) => directory,
+            None => {
+                lines.push(String::from("$ cd .."));
+                continue;
+            }
+        };
+        if directory != 0 {
+            lines.push(format!("$ cd {}", directory_name(directory)));
+        }
+        lines.push(String::from("$ ls"));
+        for &subdirectory in &subdirectories[directory] {
+            lines.push(format!("dir {}", directory_name(subdirectory)));
+        }
+        for file in 0..file_counts[directory] {
+            let extension = rng.pick(&["", ".txt", ".dat", ".log"]);
+            let file_size = rng.between(1, 2 * average_size);
+            lines.push(format!("{} file{}{}", file_size, file, extension));
+        }
+        for &subdirectory in subdirectories[directory].iter().rev() {
+            pending.push(None);
+            pending.push(Some(subdirectory));
+        }
+    }
+    lines.join("\n")
+}
+
+static EXAMPLE_INPUT: &str = "$ cd /
 $ ls
 dir a
 14848514 b.txt
@@ -200,14 +321,25 @@ $ ls
 5626152 d.ext
 7214296 k";
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), 95437);
+        assert_eq!(solve_part1(&parse_lines(EXAMPLE_INPUT).unwrap()), 95437);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), 24933642);
+        assert_eq!(
+            solve_part2(&parse_lines(EXAMPLE_INPUT).unwrap(), 70000000, 30000000),
+            24933642
+        );
+        assert_eq!(
+            solve_part2(&parse_lines(EXAMPLE_INPUT).unwrap(), 48381165, 584),
+            584
+        );
     }
 
     #[test]
@@ -277,7 +409,7 @@ $ ls
 
     #[test]
     fn test_parse_lines() {
-        let parsed_result = parse_lines(TEST_INPUT);
+        let parsed_result = parse_lines(EXAMPLE_INPUT).unwrap();
 
         // /
         let directory_top = parsed_result.borrow_mut();
@@ -349,4 +481,38 @@ $ ls
         assert_eq!(file_k.name, "k");
         assert_eq!(file_k.size, 7214296);
     }
+
+    #[test]
+    fn test_from_str() {
+        let file = "14848514 b.txt".parse::<Entry>().unwrap();
+        assert_eq!(
+            (file.entry_type, file.name.as_str(), file.size),
+            (EntryType::File, "b.txt", 14848514)
+        );
+        let directory = "dir a".parse::<Entry>().unwrap();
+        assert_eq!(directory.entry_type, EntryType::Directory);
+        assert!("big b.txt".parse::<Entry>().is_err());
+    }
+
+    #[test]
+    fn test_parse_lines_start() {
+        let error = parse_lines("$ ls\ndir a").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (1, "expected '$ cd /'")
+        );
+        assert!(parse_lines("").is_err());
+        assert!(parse_lines("$ cd /").is_ok());
+    }
+
+    #[test]
+    fn test_debug_and_eq() {
+        let tree = parse_lines(EXAMPLE_INPUT).unwrap();
+        assert!(format!("{:?}", tree).contains("h.lst"));
+        assert_eq!(tree, parse_lines(EXAMPLE_INPUT).unwrap());
+        let directory_a = Rc::clone(&tree.borrow().children[0]);
+        let parent = directory_a.borrow().parent.as_ref().unwrap().upgrade();
+        assert!(Rc::ptr_eq(&parent.unwrap(), &tree));
+        assert_ne!(*directory_a.borrow(), *tree.borrow());
+    }
 }
@@ -1,207 +1,171 @@
-use std::{cmp, collections::HashMap};
+use std::cmp;
+
+use crate::{
+    generate::Rng,
+    geometry::Direction,
+    grid::{Grid, Position},
+    Answer, Example, Params, ParseError, Solution,
+};
 
-type Coordinate = (usize, usize);
 type Tree = u32;
-type Trees = HashMap<Coordinate, Tree>;
+type Trees = Grid<Tree>;
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
-}
+pub(crate) struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+
+    type Parsed = Trees;
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(trees: &Self::Parsed, _params: &Params) -> Answer {
+        part1_impl(trees).into()
+    }
+
+    fn part2(trees: &Self::Parsed, _params: &Params) -> Answer {
+        part2_impl(trees).into()
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(21).part2(8)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
-fn parse_lines(input: &str) -> (Trees, usize, usize) {
-    let mut grid = Trees::new();
-    let mut max_x = 0;
-    let mut max_y = 0;
-    input.lines().enumerate().for_each(|(y, line)| {
-        max_y = cmp::max(max_y, y);
-        line.chars().enumerate().for_each(|(x, tree)| {
-            max_x = cmp::max(max_x, x);
-            grid.entry((x, y)).or_insert(tree.to_digit(10).unwrap());
-        });
-    });
-    (grid, max_x, max_y)
+fn parse_lines(input: &str) -> Result<Trees, ParseError> {
+    Grid::parse(input, |tree| tree.to_digit(10), "expected a digit")
 }
 
-fn is_visible_from_outside((x, y): Coordinate, trees: &Trees, max_x: usize, max_y: usize) -> bool {
-    let test_tree = trees.get(&(x, y)).unwrap();
-    (0..x).fold(true, |acc, test_x| {
-        acc && trees.get(&(test_x, y)).unwrap() < test_tree
-    }) || (0..y).fold(true, |acc, test_y| {
-        acc && trees.get(&(x, test_y)).unwrap() < test_tree
-    }) || (x + 1..=max_x).fold(true, |acc, test_x| {
-        acc && trees.get(&(test_x, y)).unwrap() < test_tree
-    }) || (y + 1..=max_y).fold(true, |acc, test_y| {
-        acc && trees.get(&(x, test_y)).unwrap() < test_tree
+fn is_visible_from_outside(position: Position, trees: &Trees) -> bool {
+    let test_tree = trees[position];
+    Direction::ALL.iter().any(|&direction| {
+        trees
+            .walk(position, direction)
+            .all(|&tree| tree < test_tree)
     })
 }
 
-fn get_total_viewing_distance(
-    (x, y): Coordinate,
-    trees: &Trees,
-    max_x: usize,
-    max_y: usize,
-) -> usize {
-    let base_tree = trees.get(&(x, y)).unwrap();
-    (0..x)
-        .rfold((true, 0), |(cont, distance), test_x| {
-            let test_tree = trees.get(&(test_x, y)).unwrap();
-            if cont {
-                (cont && base_tree > test_tree, distance + 1)
-            } else {
-                (false, distance)
-            }
-        })
-        .1
-        * (0..y)
-            .rfold((true, 0), |(cont, distance), test_y| {
-                let test_tree = trees.get(&(x, test_y)).unwrap();
-                if cont {
-                    (cont && base_tree > test_tree, distance + 1)
-                } else {
-                    (false, distance)
-                }
-            })
-            .1
-        * (x + 1..=max_x)
-            .fold((true, 0), |(cont, distance), test_x| {
-                let test_tree = trees.get(&(test_x, y)).unwrap();
-                if cont {
-                    (cont && base_tree > test_tree, distance + 1)
-                } else {
-                    (false, distance)
-                }
-            })
-            .1
-        * (y + 1..=max_y)
-            .fold((true, 0), |(cont, distance), test_y| {
-                let test_tree = trees.get(&(x, test_y)).unwrap();
-                if cont {
-                    (cont && base_tree > test_tree, distance + 1)
-                } else {
-                    (false, distance)
-                }
-            })
-            .1
+// Trees seen looking past `trees` in order, up to and including the first one as tall as
+// `base_tree`
+fn viewing_distance<'a>(base_tree: Tree, trees: impl Iterator<Item = &'a Tree>) -> usize {
+    let mut distance = 0;
+    for &tree in trees {
+        distance += 1;
+        if tree >= base_tree {
+            break;
+        }
+    }
+    distance
 }
 
-fn part1_impl(input: &str) -> usize {
-    let (trees, max_x, max_y) = parse_lines(input);
-    trees.clone().into_iter().fold(0, |acc, ((x, y), _)| {
-        if is_visible_from_outside((x, y), &trees, max_x, max_y) {
-            acc + 1
-        } else {
-            acc
-        }
-    })
+fn get_total_viewing_distance(position: Position, trees: &Trees) -> usize {
+    let base_tree = trees[position];
+    Direction::ALL
+        .iter()
+        .map(|&direction| viewing_distance(base_tree, trees.walk(position, direction)))
+        .product()
+}
+
+fn part1_impl(trees: &Trees) -> usize {
+    trees
+        .positions()
+        .filter(|&position| is_visible_from_outside(position, trees))
+        .count()
 }
 
-fn part2_impl(input: &str) -> usize {
-    let (trees, max_x, max_y) = parse_lines(input);
-    trees.clone().into_iter().fold(0, |acc, ((x, y), _)| {
-        cmp::max(
-            acc,
-            get_total_viewing_distance((x, y), &trees, max_x, max_y),
-        )
+fn part2_impl(trees: &Trees) -> usize {
+    trees.positions().fold(0, |acc, position| {
+        cmp::max(acc, get_total_viewing_distance(position, trees))
     })
 }
+
+// A forest of `size` by `size` trees
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            (0..size.max(1))
+                .map(|_| char::from_digit(rng.below(10) as u32, 10).unwrap())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+static EXAMPLE_INPUT: &str = "30373\n25512\n65332\n33549\n35390";
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    static TEST_INPUT: &str = "30373\n25512\n65332\n33549\n35390";
-
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), 21);
+        assert_eq!(part1_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 21);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), 8);
+        assert_eq!(part2_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 8);
     }
 
     #[test]
     fn test_parse_lines() {
-        let parsed_result = parse_lines(TEST_INPUT);
+        let trees = parse_lines(EXAMPLE_INPUT).unwrap();
         assert_eq!(
-            parsed_result,
-            (
-                Trees::from([
-                    // row 1
-                    ((0, 0), 3),
-                    ((1, 0), 0),
-                    ((2, 0), 3),
-                    ((3, 0), 7),
-                    ((4, 0), 3),
-                    // row 2
-                    ((0, 1), 2),
-                    ((1, 1), 5),
-                    ((2, 1), 5),
-                    ((3, 1), 1),
-                    ((4, 1), 2),
-                    // row 3
-                    ((0, 2), 6),
-                    ((1, 2), 5),
-                    ((2, 2), 3),
-                    ((3, 2), 3),
-                    ((4, 2), 2),
-                    // row 4
-                    ((0, 3), 3),
-                    ((1, 3), 3),
-                    ((2, 3), 5),
-                    ((3, 3), 4),
-                    ((4, 3), 9),
-                    // row 5
-                    ((0, 4), 3),
-                    ((1, 4), 5),
-                    ((2, 4), 3),
-                    ((3, 4), 9),
-                    ((4, 4), 0),
-                ]),
-                4,
-                4
-            )
+            trees,
+            Grid::from_rows(vec![
+                vec![3, 0, 3, 7, 3],
+                vec![2, 5, 5, 1, 2],
+                vec![6, 5, 3, 3, 2],
+                vec![3, 3, 5, 4, 9],
+                vec![3, 5, 3, 9, 0],
+            ])
+            .unwrap()
         );
+        assert_eq!(trees.to_string(), EXAMPLE_INPUT);
+        assert_eq!(parse_lines("303\n2x5").unwrap_err().column, 2);
     }
 
     #[test]
     fn test_is_visible_from_outside() {
-        let (trees, _, _) = parse_lines(TEST_INPUT);
-        assert_eq!(is_visible_from_outside((0, 0), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((1, 0), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((2, 0), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((3, 0), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((4, 0), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((0, 1), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((1, 1), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((2, 1), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((3, 1), &trees, 4, 4), false);
-        assert_eq!(is_visible_from_outside((4, 1), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((0, 2), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((1, 2), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((2, 2), &trees, 4, 4), false);
-        assert_eq!(is_visible_from_outside((3, 2), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((4, 2), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((0, 3), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((1, 3), &trees, 4, 4), false);
-        assert_eq!(is_visible_from_outside((2, 3), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((3, 3), &trees, 4, 4), false);
-        assert_eq!(is_visible_from_outside((4, 3), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((0, 4), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((1, 4), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((2, 4), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((3, 4), &trees, 4, 4), true);
-        assert_eq!(is_visible_from_outside((4, 4), &trees, 4, 4), true);
+        let trees = parse_lines(EXAMPLE_INPUT).unwrap();
+        assert!(is_visible_from_outside((0, 0), &trees));
+        assert!(is_visible_from_outside((1, 0), &trees));
+        assert!(is_visible_from_outside((2, 0), &trees));
+        assert!(is_visible_from_outside((3, 0), &trees));
+        assert!(is_visible_from_outside((4, 0), &trees));
+        assert!(is_visible_from_outside((0, 1), &trees));
+        assert!(is_visible_from_outside((1, 1), &trees));
+        assert!(is_visible_from_outside((2, 1), &trees));
+        assert!(!is_visible_from_outside((3, 1), &trees));
+        assert!(is_visible_from_outside((4, 1), &trees));
+        assert!(is_visible_from_outside((0, 2), &trees));
+        assert!(is_visible_from_outside((1, 2), &trees));
+        assert!(!is_visible_from_outside((2, 2), &trees));
+        assert!(is_visible_from_outside((3, 2), &trees));
+        assert!(is_visible_from_outside((4, 2), &trees));
+        assert!(is_visible_from_outside((0, 3), &trees));
+        assert!(!is_visible_from_outside((1, 3), &trees));
+        assert!(is_visible_from_outside((2, 3), &trees));
+        assert!(!is_visible_from_outside((3, 3), &trees));
+        assert!(is_visible_from_outside((4, 3), &trees));
+        assert!(is_visible_from_outside((0, 4), &trees));
+        assert!(is_visible_from_outside((1, 4), &trees));
+        assert!(is_visible_from_outside((2, 4), &trees));
+        assert!(is_visible_from_outside((3, 4), &trees));
+        assert!(is_visible_from_outside((4, 4), &trees));
     }
 
     #[test]
     fn test_get_total_viewing_distance() {
-        let (trees, _, _) = parse_lines(TEST_INPUT);
-        assert_eq!(get_total_viewing_distance((2, 1), &trees, 4, 4), 4);
-        assert_eq!(get_total_viewing_distance((2, 3), &trees, 4, 4), 8);
+        let trees = parse_lines(EXAMPLE_INPUT).unwrap();
+        assert_eq!(get_total_viewing_distance((2, 1), &trees), 4);
+        assert_eq!(get_total_viewing_distance((2, 3), &trees), 8);
     }
 }
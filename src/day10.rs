@@ -1,41 +1,93 @@
 use itertools::Itertools;
 
+use crate::{
+    generate::Rng, parse_error::lines, Answer, Example, Param, Params, ParseError, Solution,
+};
+
 #[derive(Debug, PartialEq)]
-enum Instruction {
-    ADDX(isize),
-    NOOP(),
+pub(crate) enum Instruction {
+    Addx(isize),
+    Noop(),
 }
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
-}
+pub(crate) struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    const TITLE: &'static str = "Cathode-Ray Tube";
+
+    // signal strengths are summed every `cycle_step` cycles from `first_cycle` to `last_cycle`
+    const PARAMS: &'static [Param] = &[
+        Param::new("first_cycle", 20).min(0),
+        Param::new("cycle_step", 40).min(1),
+        Param::new("last_cycle", 220).min(0),
+    ];
+
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(instructions: &Self::Parsed, params: &Params) -> Answer {
+        let to_check = (params.get("first_cycle")..=params.get("last_cycle"))
+            .step_by(params.get("cycle_step"))
+            .collect_vec();
+        part1_impl(instructions, &to_check).into()
+    }
+
+    fn part2(instructions: &Self::Parsed, _params: &Params) -> Answer {
+        Answer::Image(
+            part2_impl(instructions)
+                .chars()
+                .chunks(40)
+                .into_iter()
+                .map(|row| row.collect())
+                .collect_vec(),
+        )
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT)
+            .part1(13140)
+            .part2(Answer::Image(
+                [
+                    "##..##..##..##..##..##..##..##..##..##..",
+                    "###...###...###...###...###...###...###.",
+                    "####....####....####....####....####....",
+                    "#####.....#####.....#####.....#####.....",
+                    "######......######......######......####",
+                    "#######.......#######.......#######.....",
+                ]
+                .iter()
+                .map(|row| row.to_string())
+                .collect(),
+            ))]
+    }
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
-fn parse_lines(input: &str) -> Vec<Instruction> {
-    input
-        .lines()
-        .map(|line| {
-            if line.starts_with("noop") {
-                vec![Instruction::NOOP()]
-            } else {
-                let splits = line.split(" ").collect_vec();
-                let amount = splits[1].parse::<isize>().unwrap();
-                vec![Instruction::NOOP(), Instruction::ADDX(amount)]
-            }
-        })
-        .flatten()
-        .collect_vec()
+fn parse_lines(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut instructions = vec![];
+    for line in lines(input) {
+        if line.text == "noop" {
+            instructions.push(Instruction::Noop());
+        } else {
+            let amount = line.parse(line.strip_prefix("addx ")?)?;
+            instructions.extend([Instruction::Noop(), Instruction::Addx(amount)]);
+        }
+    }
+    Ok(instructions)
 }
 
-fn part1_impl(input: &str) -> isize {
-    let to_check: Vec<usize> = vec![20, 60, 100, 140, 180, 220];
+fn part1_impl(instructions: &[Instruction], to_check: &[usize]) -> isize {
     let mut current_value: isize = 1;
     let mut previous_value: isize = 1;
     let mut total = 0;
-    parse_lines(input)
+    instructions
         .iter()
         .enumerate()
         .for_each(|(cycle, instruction)| {
@@ -44,67 +96,96 @@ fn part1_impl(input: &str) -> isize {
             }
             previous_value = current_value;
             match instruction {
-                Instruction::ADDX(amount) => current_value += amount,
-                Instruction::NOOP() => (),
+                Instruction::Addx(amount) => current_value += amount,
+                Instruction::Noop() => (),
             }
         });
     total
 }
 
-fn part2_impl(input: &str) -> String {
+fn part2_impl(instructions: &[Instruction]) -> String {
     let mut next_sprite_position: isize = 1;
     let mut sprite_position: isize = 1;
     let mut result = String::from("");
-    parse_lines(input)
+    instructions
         .iter()
         .enumerate()
         .for_each(|(cycle, instruction)| {
             sprite_position = next_sprite_position;
             if (sprite_position - 1..=sprite_position + 1).contains(&((cycle as isize) % 40)) {
-                result.push_str("#");
+                result.push('#');
             } else {
-                result.push_str(".");
+                result.push('.');
             }
             match instruction {
-                Instruction::ADDX(amount) => next_sprite_position += amount,
-                Instruction::NOOP() => (),
+                Instruction::Addx(amount) => next_sprite_position += amount,
+                Instruction::Noop() => (),
             }
         });
-    println!("{:#?}", &result[0..39]);
-    println!("{:#?}", &result[40..79]);
-    println!("{:#?}", &result[80..119]);
-    println!("{:#?}", &result[120..159]);
-    println!("{:#?}", &result[160..199]);
-    println!("{:#?}", &result[200..239]);
     result
 }
 
+// `size` instructions, more when needed to last the 240 cycles the screen draws. The sprite stays
+// around the screen's 40 columns.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut lines = vec![];
+    let mut cycles = 0;
+    let mut x = 1;
+    while lines.len() < size || cycles < 240 {
+        if rng.chance(30) {
+            lines.push(String::from("noop"));
+            cycles += 1;
+        } else {
+            let amount = rng.between(-10, 10);
+            let amount = if (-1..=40).contains(&(x + amount)) {
+                amount
+            } else {
+                -amount
+            };
+            x += amount;
+            lines.push(format!("addx {}", amount));
+            cycles += 2;
+        }
+    }
+    lines.join("\n")
+}
+
+static EXAMPLE_INPUT: &str = "addx 15\naddx -11\naddx 6\naddx -3\naddx 5\naddx -1\naddx -8\naddx 13\naddx 4\nnoop\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx -35\naddx 1\naddx 24\naddx -19\naddx 1\naddx 16\naddx -11\nnoop\nnoop\naddx 21\naddx -15\nnoop\nnoop\naddx -3\naddx 9\naddx 1\naddx -3\naddx 8\naddx 1\naddx 5\nnoop\nnoop\nnoop\nnoop\nnoop\naddx -36\nnoop\naddx 1\naddx 7\nnoop\nnoop\nnoop\naddx 2\naddx 6\nnoop\nnoop\nnoop\nnoop\nnoop\naddx 1\nnoop\nnoop\naddx 7\naddx 1\nnoop\naddx -13\naddx 13\naddx 7\nnoop\naddx 1\naddx -33\nnoop\nnoop\nnoop\naddx 2\nnoop\nnoop\nnoop\naddx 8\nnoop\naddx -1\naddx 2\naddx 1\nnoop\naddx 17\naddx -9\naddx 1\naddx 1\naddx -3\naddx 11\nnoop\nnoop\naddx 1\nnoop\naddx 1\nnoop\nnoop\naddx -13\naddx -19\naddx 1\naddx 3\naddx 26\naddx -30\naddx 12\naddx -1\naddx 3\naddx 1\nnoop\nnoop\nnoop\naddx -9\naddx 18\naddx 1\naddx 2\nnoop\nnoop\naddx 9\nnoop\nnoop\nnoop\naddx -1\naddx 2\naddx -37\naddx 1\naddx 3\nnoop\naddx 15\naddx -21\naddx 22\naddx -6\naddx 1\nnoop\naddx 2\naddx 1\nnoop\naddx -10\nnoop\nnoop\naddx 20\naddx 1\naddx 2\naddx 2\naddx -6\naddx -11\nnoop\nnoop\nnoop";
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    static TEST_INPUT: &str = "addx 15\naddx -11\naddx 6\naddx -3\naddx 5\naddx -1\naddx -8\naddx 13\naddx 4\nnoop\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx -35\naddx 1\naddx 24\naddx -19\naddx 1\naddx 16\naddx -11\nnoop\nnoop\naddx 21\naddx -15\nnoop\nnoop\naddx -3\naddx 9\naddx 1\naddx -3\naddx 8\naddx 1\naddx 5\nnoop\nnoop\nnoop\nnoop\nnoop\naddx -36\nnoop\naddx 1\naddx 7\nnoop\nnoop\nnoop\naddx 2\naddx 6\nnoop\nnoop\nnoop\nnoop\nnoop\naddx 1\nnoop\nnoop\naddx 7\naddx 1\nnoop\naddx -13\naddx 13\naddx 7\nnoop\naddx 1\naddx -33\nnoop\nnoop\nnoop\naddx 2\nnoop\nnoop\nnoop\naddx 8\nnoop\naddx -1\naddx 2\naddx 1\nnoop\naddx 17\naddx -9\naddx 1\naddx 1\naddx -3\naddx 11\nnoop\nnoop\naddx 1\nnoop\naddx 1\nnoop\nnoop\naddx -13\naddx -19\naddx 1\naddx 3\naddx 26\naddx -30\naddx 12\naddx -1\naddx 3\naddx 1\nnoop\nnoop\nnoop\naddx -9\naddx 18\naddx 1\naddx 2\nnoop\nnoop\naddx 9\nnoop\nnoop\nnoop\naddx -1\naddx 2\naddx -37\naddx 1\naddx 3\nnoop\naddx 15\naddx -21\naddx 22\naddx -6\naddx 1\nnoop\naddx 2\naddx 1\nnoop\naddx -10\nnoop\nnoop\naddx 20\naddx 1\naddx 2\naddx 2\naddx -6\naddx -11\nnoop\nnoop\nnoop";
-
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), 13140);
+        assert_eq!(
+            part1_impl(
+                &parse_lines(EXAMPLE_INPUT).unwrap(),
+                &[20, 60, 100, 140, 180, 220]
+            ),
+            13140
+        );
+        assert_eq!(
+            part1_impl(&parse_lines(EXAMPLE_INPUT).unwrap(), &[20, 60]),
+            420 + 1140
+        );
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), "##..##..##..##..##..##..##..##..##..##..###...###...###...###...###...###...###.####....####....####....####....####....#####.....#####.....#####.....#####.....######......######......######......###########.......#######.......#######.....");
+        assert_eq!(part2_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), "##..##..##..##..##..##..##..##..##..##..###...###...###...###...###...###...###.####....####....####....####....####....#####.....#####.....#####.....#####.....######......######......######......###########.......#######.......#######.....");
     }
 
     #[test]
     fn test_parse_lines() {
         assert_eq!(
-            parse_lines("addx 3\nnoop\naddx 5"),
+            parse_lines("addx 3\nnoop\naddx 5").unwrap(),
             vec![
-                Instruction::NOOP(),
-                Instruction::ADDX(3),
-                Instruction::NOOP(),
-                Instruction::NOOP(),
-                Instruction::ADDX(5)
+                Instruction::Noop(),
+                Instruction::Addx(3),
+                Instruction::Noop(),
+                Instruction::Noop(),
+                Instruction::Addx(5)
             ]
         )
     }
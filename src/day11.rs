@@ -1,40 +1,64 @@
-use num::integer::Integer;
-use std::{borrow::BorrowMut, vec};
+use std::{borrow::BorrowMut, str::FromStr, vec};
 
 use itertools::Itertools;
+use num::integer::lcm;
 
-// BigUint
-type Item = u128;
+use crate::{
+    generate::Rng,
+    parse_error::{lines, Line},
+    Answer, Example, Param, Params, ParseError, Solution,
+};
+
+// Worry level of an item
+pub type Item = u128;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-enum OperationType {
+pub enum OperationType {
     Addition,
     Multiplication,
 }
 #[derive(Debug, PartialEq, Copy, Clone)]
-enum Parameter {
+pub enum Parameter {
     Old,
     Number(Item),
 }
 #[derive(Debug, PartialEq, Copy, Clone)]
-struct Operation {
-    first_parameter: Parameter,
-    operation_type: OperationType,
-    second_parameter: Parameter,
+pub struct Operation {
+    pub first_parameter: Parameter,
+    pub operation_type: OperationType,
+    pub second_parameter: Parameter,
 }
 #[derive(Debug, PartialEq, Copy, Clone)]
-struct Test {
-    divisible_by: Item,
-    true_throw_destination: usize,
-    false_throw_destination: usize,
+pub struct Test {
+    pub divisible_by: Item,
+    pub true_throw_destination: usize,
+    pub false_throw_destination: usize,
 }
 
 #[derive(Debug, PartialEq, Clone)]
-struct Monkey {
-    inspections: usize,
-    items: Vec<Item>,
-    operation: Operation,
-    test: Test,
+pub struct Monkey {
+    // items inspected so far, 0 when parsed
+    pub inspections: usize,
+    pub items: Vec<Item>,
+    pub operation: Operation,
+    pub test: Test,
+}
+
+// One `Monkey <n>:` block of the input
+impl FromStr for Monkey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Monkey, ParseError> {
+        let mut lines = lines(s).filter(|line| !line.text.trim().is_empty());
+        let header = lines
+            .next()
+            .ok_or_else(|| ParseError::at_end_of(s, "expected 'Monkey '"))?;
+        let (monkey, _) = parse_monkey(&header, &mut lines)?;
+        match lines.next() {
+            Some(line) => Err(line.error(line.text.trim_start(), "expected a single monkey")),
+            None => Ok(monkey),
+        }
+    }
 }
 
 impl Monkey {
@@ -43,88 +67,158 @@ impl Monkey {
     }
 }
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    const TITLE: &'static str = "Monkey in the Middle";
+
+    const PARAMS: &'static [Param] = &[
+        Param::new("part1_rounds", 20).min(0),
+        Param::new("part2_rounds", 10_000).min(0),
+    ];
+
+    type Parsed = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(monkeys: &Self::Parsed, params: &Params) -> Answer {
+        solve_part1(monkeys, params.get("part1_rounds")).into()
+    }
+
+    fn part2(monkeys: &Self::Parsed, params: &Params) -> Answer {
+        solve_part2(monkeys, params.get("part2_rounds")).into()
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT)
+            .part1(10605)
+            .part2(2713310158u64)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+// Reads the next `name: value` line of a monkey, returning the value
+fn next_field<'a>(
+    lines: &mut impl Iterator<Item = Line<'a>>,
+    header: &Line,
+    name: &str,
+) -> Result<(Line<'a>, &'a str), ParseError> {
+    let line = lines
+        .next()
+        .ok_or_else(|| header.error(header.end(), &format!("monkey is missing '{}'", name)))?;
+    match line.text.trim_start().split_once(": ") {
+        Some((key, value)) if key == name => Ok((line, value)),
+        _ => Err(line.error(line.text.trim_start(), &format!("expected '{}'", name))),
+    }
 }
 
-fn parse_lines(input: &str) -> Vec<Monkey> {
-    input
-        .lines()
-        .filter(|line| !line.trim().is_empty())
-        .chunks(6)
-        .into_iter()
-        .map(|mut monkey_data| {
-            let items = monkey_data
-                .nth(1)
-                .unwrap()
-                .split(": ")
-                .nth(1)
-                .unwrap()
-                .split(", ")
-                .map(|x| x.parse::<Item>().unwrap())
-                .collect_vec();
-            let operation_data = monkey_data
-                .nth(0)
-                .unwrap()
-                .split(": new = ")
-                .nth(1)
-                .unwrap();
-            let first_parameter = operation_data.split(" ").nth(0).unwrap();
-            let operation_type = operation_data.split(" ").nth(1).unwrap();
-            let second_parameter = operation_data.split(" ").nth(2).unwrap();
-            Monkey {
-                inspections: 0,
-                items: items,
-                operation: Operation {
-                    first_parameter: if first_parameter.starts_with("old") {
-                        Parameter::Old
-                    } else {
-                        Parameter::Number(first_parameter.parse::<Item>().unwrap())
-                    },
-                    operation_type: if operation_type == "*" {
-                        OperationType::Multiplication
-                    } else {
-                        OperationType::Addition
-                    },
-                    second_parameter: if second_parameter.starts_with("old") {
-                        Parameter::Old
-                    } else {
-                        Parameter::Number(second_parameter.parse::<Item>().unwrap())
-                    },
-                },
-                test: Test {
-                    divisible_by: monkey_data
-                        .nth(0)
-                        .unwrap()
-                        .split(" divisible by ")
-                        .nth(1)
-                        .unwrap()
-                        .parse::<Item>()
-                        .unwrap(),
-                    true_throw_destination: monkey_data
-                        .nth(0)
-                        .unwrap()
-                        .split("throw to monkey ")
-                        .nth(1)
-                        .unwrap()
-                        .parse::<usize>()
-                        .unwrap(),
-                    false_throw_destination: monkey_data
-                        .nth(0)
-                        .unwrap()
-                        .split("throw to monkey ")
-                        .nth(1)
-                        .unwrap()
-                        .parse::<usize>()
-                        .unwrap(),
-                },
-            }
-        })
+fn strip_value_prefix<'a>(
+    line: &Line,
+    value: &'a str,
+    prefix: &str,
+) -> Result<&'a str, ParseError> {
+    value
+        .strip_prefix(prefix)
+        .ok_or_else(|| line.error(value, &format!("expected '{}'", prefix)))
+}
+
+fn parse_parameter(line: &Line, parameter: &str) -> Result<Parameter, ParseError> {
+    if parameter == "old" {
+        Ok(Parameter::Old)
+    } else {
+        Ok(Parameter::Number(line.parse(parameter)?))
+    }
+}
+
+// A throw target as it appears in the input, only checkable once every monkey is known
+type Throw<'a> = (Line<'a>, &'a str, usize);
+
+// The monkey and the lines of its two throw targets
+fn parse_monkey<'a>(
+    header: &Line,
+    lines: &mut impl Iterator<Item = Line<'a>>,
+) -> Result<(Monkey, [Throw<'a>; 2]), ParseError> {
+    if !header.text.trim_start().starts_with("Monkey ") {
+        return Err(header.error(header.text.trim_start(), "expected 'Monkey '"));
+    }
+
+    let (line, value) = next_field(lines, header, "Starting items")?;
+    let items = value
+        .split(", ")
+        .map(|item| line.parse(item))
+        .collect::<Result<Vec<Item>, _>>()?;
+
+    let (line, value) = next_field(lines, header, "Operation")?;
+    let operation = match strip_value_prefix(&line, value, "new = ")?
+        .split(' ')
         .collect_vec()
+        .as_slice()
+    {
+        [first_parameter, operation_type, second_parameter] => Operation {
+            first_parameter: parse_parameter(&line, first_parameter)?,
+            operation_type: match *operation_type {
+                "*" => OperationType::Multiplication,
+                "+" => OperationType::Addition,
+                _ => return Err(line.error(operation_type, "expected '*' or '+'")),
+            },
+            second_parameter: parse_parameter(&line, second_parameter)?,
+        },
+        _ => return Err(line.error(value, "expected 'new = <a> <op> <b>'")),
+    };
+
+    let (line, value) = next_field(lines, header, "Test")?;
+    let divisor = strip_value_prefix(&line, value, "divisible by ")?;
+    let divisible_by = line.parse(divisor)?;
+    if divisible_by == 0 {
+        return Err(line.error(divisor, "expected a divisor above 0"));
+    }
+    let mut throw = |name| -> Result<Throw<'a>, ParseError> {
+        let (line, value) = next_field(lines, header, name)?;
+        let destination = strip_value_prefix(&line, value, "throw to monkey ")?;
+        Ok((line, destination, line.parse(destination)?))
+    };
+    let on_true = throw("If true")?;
+    let on_false = throw("If false")?;
+
+    let monkey = Monkey {
+        inspections: 0,
+        items,
+        operation,
+        test: Test {
+            divisible_by,
+            true_throw_destination: on_true.2,
+            false_throw_destination: on_false.2,
+        },
+    };
+    Ok((monkey, [on_true, on_false]))
+}
+
+// The monkeys in the order of their numbers
+pub fn parse_lines(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut lines = lines(input).filter(|line| !line.text.trim().is_empty());
+    let mut monkeys = vec![];
+    let mut throws = vec![];
+    while let Some(header) = lines.next() {
+        let (monkey, monkey_throws) = parse_monkey(&header, &mut lines)?;
+        monkeys.push(monkey);
+        throws.extend(monkey_throws);
+    }
+    match throws
+        .into_iter()
+        .find(|&(_, _, destination)| destination >= monkeys.len())
+    {
+        Some((line, destination, _)) => Err(line.error(
+            destination,
+            &format!("expected a monkey below {}", monkeys.len()),
+        )),
+        None => Ok(monkeys),
+    }
 }
 
 fn get_parameter(parameter: Parameter, item: Item) -> Item {
@@ -134,24 +228,26 @@ fn get_parameter(parameter: Parameter, item: Item) -> Item {
     }
 }
 
-fn perform_operation(operation: Operation, item: Item) -> Item {
+// `None` when the new worry level does not fit an `Item`
+fn perform_operation(operation: Operation, item: Item) -> Option<Item> {
+    let first = get_parameter(operation.first_parameter, item);
+    let second = get_parameter(operation.second_parameter, item);
     match operation.operation_type {
-        OperationType::Addition => {
-            get_parameter(operation.first_parameter, item)
-                + get_parameter(operation.second_parameter, item)
-        }
-        OperationType::Multiplication => {
-            get_parameter(operation.first_parameter, item)
-                * get_parameter(operation.second_parameter, item)
-        }
+        OperationType::Addition => first.checked_add(second),
+        OperationType::Multiplication => first.checked_mul(second),
     }
 }
 
 fn perform_test(test: Test, item: Item) -> bool {
-    item % test.divisible_by == 0
+    item.is_multiple_of(test.divisible_by)
 }
 
-fn perform_round(monkeys: &mut Vec<Monkey>, should_divide: bool, lcd: u128) -> &mut Vec<Monkey> {
+// `None` when a worry level outgrows an `Item`
+fn perform_round(
+    monkeys: &mut Vec<Monkey>,
+    should_divide: bool,
+    lcd: u128,
+) -> Option<&mut Vec<Monkey>> {
     for monkey_id in 0..monkeys.len() {
         let mut move_items: Vec<(usize, Item)> = vec![];
         {
@@ -159,9 +255,12 @@ fn perform_round(monkeys: &mut Vec<Monkey>, should_divide: bool, lcd: u128) -> &
             monkey.inspections += monkey.items.len();
             for item_id in 0..monkey.items.len() {
                 let item = monkey.items[item_id];
-                let calculated_item = perform_operation(monkey.operation, item);
+                // part 1 follows worry levels exactly, so enough rounds outgrow an `Item`
+                let calculated_item = perform_operation(monkey.operation, item)?;
+                // dividing does not commute with the remainder, so levels are only reduced
+                // modulo the divisors when they are not divided
                 let result_item = if should_divide {
-                    calculated_item.div_floor(&3) % lcd
+                    calculated_item / 3
                 } else {
                     calculated_item % lcd
                 };
@@ -179,66 +278,130 @@ fn perform_round(monkeys: &mut Vec<Monkey>, should_divide: bool, lcd: u128) -> &
                 monkeys[*destination].add_item(*item);
             });
     }
-    monkeys
+    Some(monkeys)
 }
 
-fn part1_impl(input: &str) -> usize {
-    let mut parsed_monkeys = parse_lines(input);
+// Monkey business after `rounds` with worry levels divided by 3 after every inspection, `None`
+// once they outgrow an `Item`
+pub fn solve_part1(monkeys: &[Monkey], rounds: usize) -> Option<usize> {
+    let mut parsed_monkeys = monkeys.to_vec();
     let lcd = parsed_monkeys
         .iter_mut()
-        .fold(1, |acc, monkey| acc * monkey.test.divisible_by);
+        .fold(1, |acc, monkey| lcm(acc, monkey.test.divisible_by));
     let mut monkeys = parsed_monkeys.borrow_mut();
-    for _ in 0..20 {
-        monkeys = perform_round(monkeys, true, lcd);
-    }
-    monkeys
-        .iter()
-        .map(|monkey| monkey.inspections)
-        .sorted()
-        .rev()
-        .take(2)
-        .product()
+    for _ in 0..rounds {
+        monkeys = perform_round(monkeys, true, lcd)?;
+    }
+    Some(
+        monkeys
+            .iter()
+            .map(|monkey| monkey.inspections)
+            .sorted()
+            .rev()
+            .take(2)
+            .product(),
+    )
 }
 
-fn part2_impl(input: &str) -> usize {
-    let mut parsed_monkeys = parse_lines(input);
+// Monkey business after `rounds` without dividing worry levels
+pub fn solve_part2(monkeys: &[Monkey], rounds: usize) -> Option<usize> {
+    let mut parsed_monkeys = monkeys.to_vec();
     let lcd = parsed_monkeys
         .iter_mut()
-        .fold(1, |acc, monkey| acc * monkey.test.divisible_by);
+        .fold(1, |acc, monkey| lcm(acc, monkey.test.divisible_by));
     let mut monkeys = parsed_monkeys.borrow_mut();
-    for _ in 0..10_000 {
-        monkeys = perform_round(monkeys, false, lcd);
-    }
-    monkeys
-        .iter()
-        .map(|monkey| monkey.inspections)
-        .sorted()
-        .rev()
-        .take(2)
-        .product()
+    for _ in 0..rounds {
+        monkeys = perform_round(monkeys, false, lcd)?;
+    }
+    Some(
+        monkeys
+            .iter()
+            .map(|monkey| monkey.inspections)
+            .sorted()
+            .rev()
+            .take(2)
+            .product(),
+    )
 }
 
+// `size` monkeys, at least three, like in the puzzle: monkey 0 squares worry levels and the
+// others add or multiply small numbers, all testing divisibility by a small prime. Nothing
+// throws back to monkey 0 and apart from monkey 1 everyone throws to a monkey that already
+// had its turn, so no item is squared twice or inspected more than twice a round and part 1
+// stays within `Item`.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let count = size.max(3);
+    (0..count)
+        .map(|monkey| {
+            let items = (0..rng.between(1, 6))
+                .map(|_| rng.between(50, 99))
+                .join(", ");
+            let operation = if monkey == 0 {
+                String::from("old * old")
+            } else if rng.chance(50) {
+                format!("old + {}", rng.between(1, 8))
+            } else {
+                format!("old * {}", rng.between(2, 13))
+            };
+            let (low, high) = match monkey {
+                0 => (1, count - 1),
+                1 => (2, count - 1),
+                _ => (1, monkey - 1),
+            };
+            let mut target = || rng.between(low as i64, high as i64);
+            let (on_true, on_false) = (target(), target());
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}",
+                monkey,
+                items,
+                operation,
+                rng.pick(&[2, 3, 5, 7, 11, 13, 17, 19, 23]),
+                on_true,
+                on_false
+            )
+        })
+        .join("\n\n")
+}
+
+static EXAMPLE_INPUT: &str = "Monkey 0:\n    Starting items: 79, 98\n    Operation: new = old * 19\n    Test: divisible by 23\n      If true: throw to monkey 2\n      If false: throw to monkey 3\n  \n  Monkey 1:\n    Starting items: 54, 65, 75, 74\n    Operation: new = old + 6\n    Test: divisible by 19\n      If true: throw to monkey 2\n      If false: throw to monkey 0\n  \n  Monkey 2:\n    Starting items: 79, 60, 97\n    Operation: new = old * old\n    Test: divisible by 13\n      If true: throw to monkey 1\n      If false: throw to monkey 3\n  \n  Monkey 3:\n    Starting items: 74\n    Operation: new = old + 3\n    Test: divisible by 17\n      If true: throw to monkey 0\n      If false: throw to monkey 1";
+
 #[cfg(test)]
 mod tests {
-    use std::vec;
+    use std::{mem, vec};
 
-    use super::*;
+    use num::{BigUint, Zero};
+    use proptest::prelude::*;
 
-    static TEST_INPUT: &str = "Monkey 0:\n    Starting items: 79, 98\n    Operation: new = old * 19\n    Test: divisible by 23\n      If true: throw to monkey 2\n      If false: throw to monkey 3\n  \n  Monkey 1:\n    Starting items: 54, 65, 75, 74\n    Operation: new = old + 6\n    Test: divisible by 19\n      If true: throw to monkey 2\n      If false: throw to monkey 0\n  \n  Monkey 2:\n    Starting items: 79, 60, 97\n    Operation: new = old * old\n    Test: divisible by 13\n      If true: throw to monkey 1\n      If false: throw to monkey 3\n  \n  Monkey 3:\n    Starting items: 74\n    Operation: new = old + 3\n    Test: divisible by 17\n      If true: throw to monkey 0\n      If false: throw to monkey 1";
+    use super::*;
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), 10605);
+        assert_eq!(
+            solve_part1(&parse_lines(EXAMPLE_INPUT).unwrap(), 20),
+            Some(10605)
+        );
+    }
+
+    #[test]
+    fn test_part1_overflow() {
+        assert_eq!(solve_part1(&parse_lines(EXAMPLE_INPUT).unwrap(), 200), None);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), 2713310158);
+        assert_eq!(
+            solve_part2(&parse_lines(EXAMPLE_INPUT).unwrap(), 10_000),
+            Some(2713310158)
+        );
+        assert_eq!(
+            solve_part2(&parse_lines(EXAMPLE_INPUT).unwrap(), 20),
+            Some(103 * 99)
+        );
     }
 
     #[test]
     fn test_parse_lines() {
-        let monkeys = parse_lines(TEST_INPUT);
+        let monkeys = parse_lines(EXAMPLE_INPUT).unwrap();
         assert_eq!(
             monkeys[0],
             Monkey {
@@ -309,6 +472,16 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_parse_lines_errors() {
+        let error = parse_lines(&EXAMPLE_INPUT.replace("by 13", "by 0")).unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (18, "0"));
+        assert_eq!(error.message, "expected a divisor above 0");
+        let error = parse_lines(&EXAMPLE_INPUT.replace("monkey 0", "monkey 4")).unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (13, "4"));
+        assert_eq!(error.message, "expected a monkey below 4");
+    }
+
     #[test]
     fn test_perform_round() {
         let mut monkeys = vec![
@@ -370,8 +543,8 @@ mod tests {
             },
         ];
         assert_eq!(
-            *perform_round(&mut monkeys, true, 23 * 19 * 13 * 17),
-            vec![
+            perform_round(&mut monkeys, true, 23 * 19 * 13 * 17).cloned(),
+            Some(vec![
                 Monkey {
                     inspections: 2,
                     items: vec![20, 23, 27, 26],
@@ -428,7 +601,7 @@ mod tests {
                         false_throw_destination: 1,
                     },
                 },
-            ]
+            ])
         )
     }
 
@@ -449,7 +622,7 @@ mod tests {
                 },
                 79
             ),
-            1501
+            Some(1501)
         );
         assert_eq!(
             perform_operation(
@@ -460,7 +633,7 @@ mod tests {
                 },
                 54
             ),
-            60
+            Some(60)
         );
         assert_eq!(
             perform_operation(
@@ -471,33 +644,179 @@ mod tests {
                 },
                 79
             ),
-            6241
+            Some(6241)
+        );
+        assert_eq!(
+            perform_operation(
+                Operation {
+                    first_parameter: Parameter::Old,
+                    operation_type: OperationType::Multiplication,
+                    second_parameter: Parameter::Old
+                },
+                Item::MAX / 2
+            ),
+            None
         )
     }
 
     #[test]
     fn test_perform_test() {
+        assert!(perform_test(
+            Test {
+                divisible_by: 17,
+                true_throw_destination: 0,
+                false_throw_destination: 1
+            },
+            34
+        ));
+        assert!(!perform_test(
+            Test {
+                divisible_by: 17,
+                true_throw_destination: 0,
+                false_throw_destination: 1
+            },
+            32
+        ));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let monkey = "Monkey 3:\n  Starting items: 74\n  Operation: new = old + 3\n  Test: divisible by 17\n    If true: throw to monkey 0\n    If false: throw to monkey 1"
+            .parse::<Monkey>()
+            .unwrap();
+        assert_eq!(monkey.items, vec![74]);
+        assert_eq!(monkey.test.divisible_by, 17);
         assert_eq!(
-            perform_test(
-                Test {
-                    divisible_by: 17,
-                    true_throw_destination: 0,
-                    false_throw_destination: 1
-                },
-                34
-            ),
-            true
-        );
-        assert_eq!(
-            perform_test(
-                Test {
-                    divisible_by: 17,
-                    true_throw_destination: 0,
-                    false_throw_destination: 1
-                },
-                32
-            ),
-            false
+            EXAMPLE_INPUT.parse::<Monkey>().unwrap_err().message,
+            "expected a single monkey"
         );
+        assert!("".parse::<Monkey>().is_err());
+    }
+
+    // Follows every worry level exactly, without reducing modulo the product of the divisors,
+    // giving up once a level exceeds `limit`
+    fn reference_monkey_business(
+        monkeys: &[Monkey],
+        rounds: usize,
+        divide: bool,
+        limit: Option<&BigUint>,
+    ) -> Option<usize> {
+        let mut items = monkeys
+            .iter()
+            .map(|monkey| {
+                monkey
+                    .items
+                    .iter()
+                    .map(|&item| BigUint::from(item))
+                    .collect()
+            })
+            .collect::<Vec<Vec<_>>>();
+        let mut inspections = vec![0; monkeys.len()];
+        for _ in 0..rounds {
+            for (id, monkey) in monkeys.iter().enumerate() {
+                for item in mem::take(&mut items[id]) {
+                    inspections[id] += 1;
+                    let value = |parameter| match parameter {
+                        Parameter::Old => item.clone(),
+                        Parameter::Number(n) => BigUint::from(n),
+                    };
+                    let (first, second) = (
+                        value(monkey.operation.first_parameter),
+                        value(monkey.operation.second_parameter),
+                    );
+                    let mut worry = match monkey.operation.operation_type {
+                        OperationType::Addition => first + second,
+                        OperationType::Multiplication => first * second,
+                    };
+                    if limit.is_some_and(|limit| worry > *limit) {
+                        return None;
+                    }
+                    if divide {
+                        worry /= 3_u32;
+                    }
+                    let destination = if (&worry % monkey.test.divisible_by).is_zero() {
+                        monkey.test.true_throw_destination
+                    } else {
+                        monkey.test.false_throw_destination
+                    };
+                    items[destination].push(worry);
+                }
+            }
+        }
+        Some(inspections.iter().sorted().rev().take(2).product())
+    }
+
+    fn operation() -> impl Strategy<Value = Operation> {
+        let operation = |operation_type, second_parameter| Operation {
+            first_parameter: Parameter::Old,
+            operation_type,
+            second_parameter,
+        };
+        prop_oneof![
+            (1..10_u128)
+                .prop_map(move |n| operation(OperationType::Addition, Parameter::Number(n))),
+            (2..20_u128)
+                .prop_map(move |n| operation(OperationType::Multiplication, Parameter::Number(n))),
+            Just(operation(OperationType::Multiplication, Parameter::Old)),
+        ]
+    }
+
+    // Two to four monkeys that never throw to themselves. Only the first one may square
+    // worry levels, or the exact ones get too big to follow.
+    fn monkeys() -> impl Strategy<Value = Vec<Monkey>> {
+        (2..=4_usize)
+            .prop_flat_map(|count| {
+                let monkey = (
+                    prop::collection::vec(1..100_u128, 0..4),
+                    operation(),
+                    prop::sample::select(vec![2, 3, 5, 7, 11, 13, 17, 19, 23]),
+                    1..count,
+                    1..count,
+                );
+                prop::collection::vec(monkey, count)
+            })
+            .prop_map(|monkeys| {
+                let count = monkeys.len();
+                monkeys
+                    .into_iter()
+                    .enumerate()
+                    .map(
+                        |(id, (items, mut operation, divisible_by, on_true, on_false))| {
+                            if id > 0 && operation.second_parameter == Parameter::Old {
+                                operation.second_parameter = Parameter::Number(2);
+                            }
+                            Monkey {
+                                inspections: 0,
+                                items,
+                                operation,
+                                test: Test {
+                                    divisible_by,
+                                    true_throw_destination: (id + on_true) % count,
+                                    false_throw_destination: (id + on_false) % count,
+                                },
+                            }
+                        },
+                    )
+                    .collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn part1_matches_reference(monkeys in monkeys(), rounds in 0..20_usize) {
+            // without the remainder trick part 1 is only exact while worry levels fit an `Item`
+            let limit = BigUint::from(Item::MAX);
+            let expected = reference_monkey_business(&monkeys, rounds, true, Some(&limit));
+            prop_assume!(expected.is_some(), "worry levels outgrow an Item");
+            prop_assert_eq!(solve_part1(&monkeys, rounds), expected);
+        }
+
+        #[test]
+        fn part2_matches_reference(monkeys in monkeys(), rounds in 0..10_usize) {
+            prop_assert_eq!(
+                solve_part2(&monkeys, rounds),
+                reference_monkey_business(&monkeys, rounds, false, None)
+            );
+        }
     }
 }
@@ -1,327 +1,317 @@
-use std::{
-    cmp,
-    collections::{HashMap, HashSet, VecDeque},
+use crate::{
+    generate::Rng,
+    grid::{Grid, Position},
+    search::{self, Path},
+    Answer, Example, Params, ParseError, Solution,
 };
 
 type Height = char;
-type Coordinate = (usize, usize);
-type HeightMap = HashMap<Coordinate, Height>;
+type HeightMap = Grid<Height>;
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
-}
+pub(crate) struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u32 = 12;
+    const TITLE: &'static str = "Hill Climbing Algorithm";
+
+    type Parsed = (HeightMap, Position, Position);
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(height_map: &Self::Parsed, _params: &Params) -> Answer {
+        part1_impl(height_map).into()
+    }
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+    fn part2(height_map: &Self::Parsed, _params: &Params) -> Answer {
+        part2_impl(height_map).into()
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(31).part2(29)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
-fn parse_lines(input: &str) -> (HeightMap, usize, usize, Coordinate, Coordinate) {
-    let mut height_map = HeightMap::new();
-    let mut max_x = 0;
-    let mut max_y = 0;
-    let mut start_coordinate: Coordinate = (0, 0);
-    let mut end_coordinate: Coordinate = (0, 0);
-    input.lines().enumerate().for_each(|(y, line)| {
-        max_y = cmp::max(max_y, y);
-        line.chars().enumerate().for_each(|(x, height)| {
-            max_x = cmp::max(max_x, x);
-            if height == 'S' {
-                start_coordinate = (x, y);
-                height_map.entry(start_coordinate).or_insert('a');
-            } else if height == 'E' {
-                end_coordinate = (x, y);
-                height_map.entry(end_coordinate).or_insert('z');
-            } else {
-                height_map.entry((x, y)).or_insert(height);
-            }
-        });
+fn parse_lines(input: &str) -> Result<(HeightMap, Position, Position), ParseError> {
+    let marked = Grid::parse(
+        input,
+        |height| (height.is_ascii_lowercase() || height == 'S' || height == 'E').then_some(height),
+        "expected a height from 'a' to 'z', 'S' or 'E'",
+    )?;
+    let start_coordinate = marked
+        .position(|&height| height == 'S')
+        .ok_or_else(|| ParseError::at_end_of(input, "missing start 'S'"))?;
+    let end_coordinate = marked
+        .position(|&height| height == 'E')
+        .ok_or_else(|| ParseError::at_end_of(input, "missing end 'E'"))?;
+    let height_map = marked.map(|&height| match height {
+        'S' => 'a',
+        'E' => 'z',
+        height => height,
     });
-    (height_map, max_x, max_y, start_coordinate, end_coordinate)
+    Ok((height_map, start_coordinate, end_coordinate))
 }
 
 fn is_one_higher_or_equal(c1: char, c2: char) -> bool {
     c1 as u32 + 1 == c2 as u32 || c1 as u32 >= c2 as u32
 }
 
-fn is_one_lower_or_equal(c2: char, c1: char) -> bool {
-    is_one_higher_or_equal(c1, c2)
+fn get_adjacents(coordinate: Position, height_map: &HeightMap) -> Vec<Position> {
+    let test_coordinate = height_map[coordinate];
+    height_map
+        .neighbours4(coordinate)
+        .filter(|&adjacent| is_one_higher_or_equal(test_coordinate, height_map[adjacent]))
+        .collect()
 }
 
-fn get_adjacents(
-    (x, y): Coordinate,
+// Shortest climb from any of `start_coordinates` to `end_coordinate`, keeping where the search
+// has been in a grid rather than a `HashMap` as that is several times faster
+fn climb(
     height_map: &HeightMap,
-    max_x: usize,
-    max_y: usize,
-    is_valid_adjacent: fn(char, char) -> bool,
-) -> Vec<Coordinate> {
-    let mut result = vec![];
-    let test_coordinate = height_map.get(&(x, y)).unwrap();
-    for test_x in cmp::max(1, x) - 1..=cmp::min(x + 1, max_x) {
-        if is_valid_adjacent(*test_coordinate, *(height_map.get(&(test_x, y)).unwrap()))
-            && x != test_x
-        {
-            result.push((test_x, y));
-        }
-    }
-    for test_y in cmp::max(1, y) - 1..=cmp::min(y + 1, max_y) {
-        if is_valid_adjacent(*test_coordinate, *(height_map.get(&(x, test_y)).unwrap()))
-            && y != test_y
-        {
-            result.push((x, test_y));
-        }
-    }
-    result
+    start_coordinates: impl IntoIterator<Item = Position>,
+    end_coordinate: Position,
+) -> Option<Path<Position, usize>> {
+    search::bfs_with(
+        Grid::new(height_map.width(), height_map.height(), None),
+        start_coordinates,
+        |&coordinate| get_adjacents(coordinate, height_map),
+        |&coordinate| coordinate == end_coordinate,
+    )
 }
 
-fn bfs(
-    height_map: HeightMap,
-    start_coordinate: Coordinate,
-    max_x: usize,
-    max_y: usize,
-    is_end_coordinate: &dyn Fn(Coordinate) -> bool,
-    is_valid_adjacent: fn(char, char) -> bool,
-) -> Option<Vec<Option<Coordinate>>> {
-    let mut queue = VecDeque::new();
-    let mut visited_vertices = HashSet::with_capacity(height_map.len());
-    let mut prev: HashMap<Coordinate, Option<Coordinate>> =
-        HashMap::with_capacity(height_map.len());
-
-    queue.push_back(start_coordinate);
-    visited_vertices.insert(start_coordinate);
-
-    let mut last_node = start_coordinate;
-    while let Some(node) = queue.pop_front() {
-        if is_end_coordinate(node) {
-            last_node = node;
-            break;
-        }
-
-        for adjacent_node in get_adjacents(node, &height_map, max_x, max_y, is_valid_adjacent) {
-            if !visited_vertices.contains(&adjacent_node) {
-                visited_vertices.insert(adjacent_node);
-                prev.insert(adjacent_node, Some(node));
-                queue.push_back(adjacent_node);
-            }
-        }
-    }
-
-    let mut path = Vec::new();
-    let mut at = Some(last_node);
-    while at != None {
-        path.push(at);
-        if prev.contains_key(&at.unwrap()) {
-            at = *prev.get(&at.unwrap()).unwrap();
-        } else {
-            at = None;
-        }
-    }
-    path.reverse();
-
-    return match path[0] {
-        Some(x) if x == start_coordinate => Some(path),
-        _ => None,
-    };
+fn part1_impl(
+    (height_map, start_coordinate, end_coordinate): &(HeightMap, Position, Position),
+) -> usize {
+    climb(height_map, [*start_coordinate], *end_coordinate)
+        .expect("No path found")
+        .cost
 }
 
-fn part1_impl(input: &str) -> usize {
-    let (heightmap, max_x, max_y, start_coordinate, end_coordinate) = parse_lines(input);
-
-    let comparator = |coordinate| end_coordinate == coordinate;
-    match bfs(
-        heightmap,
-        start_coordinate,
-        max_x,
-        max_y,
-        &comparator,
-        is_one_higher_or_equal,
-    ) {
-        Some(path) => path.len() - 1,
-        None => panic!("No path found"),
-    }
+// Climbs from every lowest square at once
+fn part2_impl((height_map, _, end_coordinate): &(HeightMap, Position, Position)) -> usize {
+    let lowest = height_map
+        .positions()
+        .filter(|&coordinate| height_map[coordinate] == 'a');
+    climb(height_map, lowest, *end_coordinate)
+        .expect("No path found")
+        .cost
 }
 
-fn part2_impl(input: &str) -> usize {
-    let (heightmap, max_x, max_y, _, end_coordinate) = parse_lines(input);
-
-    let comparator = |coordinate| *heightmap.get(&coordinate).unwrap() == 'a';
-    match bfs(
-        heightmap.clone(),
-        end_coordinate,
-        max_x,
-        max_y,
-        &comparator,
-        is_one_lower_or_equal,
-    ) {
-        Some(path) => path.len() - 1,
-        None => panic!("No path found"),
+// A map `size` columns wide and a quarter as high, at least 6 by 5, that climbs from 'a' to 'z'
+// along a path snaking through the rows, ending at 'E'. Rows that meet are mostly cliffs that
+// only work as shortcuts downwards. The odd dip into a lower height never blocks the way.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(6);
+    let height = (size / 4).max(5);
+    let cells = width * height;
+    let heights = (0..cells)
+        .map(|step| (step * 26 / cells) as u8)
+        .collect::<Vec<_>>();
+    let start = rng.below(cells.div_ceil(26));
+    let mut rows = vec![vec!['a'; width]; height];
+    for (step, &level) in heights.iter().enumerate() {
+        let (y, x) = (step / width, step % width);
+        let x = if y % 2 == 0 { x } else { width - 1 - x };
+        let level_around = step > 0
+            && step + 1 < cells
+            && heights[step - 1] == level
+            && heights[step + 1] == level;
+        rows[y][x] = if step == cells - 1 {
+            'E'
+        } else if step == start {
+            'S'
+        } else if level > 0 && level_around && rng.chance(2) {
+            (b'a' + level - 1) as char
+        } else {
+            (b'a' + level) as char
+        };
     }
+    rows.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
+static EXAMPLE_INPUT: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi";
+
 #[cfg(test)]
 mod tests {
-    use std::vec;
+    use std::{
+        collections::{HashMap, VecDeque},
+        vec,
+    };
 
-    use super::*;
+    use proptest::prelude::*;
 
-    static TEST_INPUT: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi";
+    use super::*;
+    use crate::generate::Rng;
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), 31);
+        assert_eq!(part1_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 31);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), 29);
+        assert_eq!(part2_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 29);
     }
 
     #[test]
     fn test_parse_lines() {
+        let (height_map, start_coordinate, end_coordinate) = parse_lines(EXAMPLE_INPUT).unwrap();
         assert_eq!(
-            parse_lines(TEST_INPUT),
-            (
-                HeightMap::from([
-                    ((0, 0), 'a'),
-                    ((0, 1), 'a'),
-                    ((0, 2), 'a'),
-                    ((0, 3), 'a'),
-                    ((0, 4), 'a'),
-                    ((1, 0), 'a'),
-                    ((1, 1), 'b'),
-                    ((1, 2), 'c'),
-                    ((1, 3), 'c'),
-                    ((1, 4), 'b'),
-                    ((2, 0), 'b'),
-                    ((2, 1), 'c'),
-                    ((2, 2), 'c'),
-                    ((2, 3), 'c'),
-                    ((2, 4), 'd'),
-                    ((3, 0), 'q'),
-                    ((3, 1), 'r'),
-                    ((3, 2), 's'),
-                    ((3, 3), 't'),
-                    ((3, 4), 'e'),
-                    ((4, 0), 'p'),
-                    ((4, 1), 'y'),
-                    ((4, 2), 'z'),
-                    ((4, 3), 'u'),
-                    ((4, 4), 'f'),
-                    ((5, 0), 'o'),
-                    ((5, 1), 'x'),
-                    ((5, 2), 'z'),
-                    ((5, 3), 'v'),
-                    ((5, 4), 'g'),
-                    ((6, 0), 'n'),
-                    ((6, 1), 'x'),
-                    ((6, 2), 'x'),
-                    ((6, 3), 'w'),
-                    ((6, 4), 'h'),
-                    ((7, 0), 'm'),
-                    ((7, 1), 'l'),
-                    ((7, 2), 'k'),
-                    ((7, 3), 'j'),
-                    ((7, 4), 'i'),
-                ]),
-                7,
-                4,
-                (0, 0),
-                (5, 2)
-            )
-        )
+            height_map.to_string(),
+            "aabqponm\nabcryxxl\naccszzxk\nacctuvwj\nabdefghi"
+        );
+        assert_eq!((start_coordinate, end_coordinate), ((0, 0), (5, 2)));
+        assert_eq!(parse_lines("Sab\naBE").unwrap_err().column, 2);
+        assert_eq!(
+            parse_lines("Sab\nabc").unwrap_err().message,
+            "missing end 'E'"
+        );
     }
 
     #[test]
     fn test_is_one_higher() {
-        assert_eq!(is_one_higher_or_equal('a', 'b'), true);
-        assert_eq!(is_one_higher_or_equal('a', 'c'), false);
-        assert_eq!(is_one_higher_or_equal('a', 'f'), false);
-        assert_eq!(is_one_higher_or_equal('b', 'a'), true);
-        assert_eq!(is_one_higher_or_equal('f', 'a'), true);
-        assert_eq!(is_one_higher_or_equal('b', 'b'), true);
-        assert_eq!(is_one_higher_or_equal('z', 'z'), true);
+        assert!(is_one_higher_or_equal('a', 'b'));
+        assert!(!is_one_higher_or_equal('a', 'c'));
+        assert!(!is_one_higher_or_equal('a', 'f'));
+        assert!(is_one_higher_or_equal('b', 'a'));
+        assert!(is_one_higher_or_equal('f', 'a'));
+        assert!(is_one_higher_or_equal('b', 'b'));
+        assert!(is_one_higher_or_equal('z', 'z'));
     }
 
     #[test]
     fn test_get_adjacents() {
-        let (height_map, max_x, max_y, _, _) = parse_lines(TEST_INPUT);
-        assert_eq!(
-            get_adjacents((0, 0), &height_map, max_x, max_y, is_one_higher_or_equal),
-            vec![(1, 0), (0, 1)]
-        );
+        let (height_map, _, _) = parse_lines(EXAMPLE_INPUT).unwrap();
+        assert_eq!(get_adjacents((0, 0), &height_map), vec![(1, 0), (0, 1)]);
         assert_eq!(
-            get_adjacents((1, 1), &height_map, max_x, max_y, is_one_higher_or_equal),
+            get_adjacents((1, 1), &height_map),
             vec![(0, 1), (2, 1), (1, 0), (1, 2)]
         );
         assert_eq!(
-            get_adjacents((2, 2), &height_map, max_x, max_y, is_one_higher_or_equal),
+            get_adjacents((2, 2), &height_map),
             vec![(1, 2), (2, 1), (2, 3)]
         );
         assert_eq!(
-            get_adjacents((4, 2), &height_map, max_x, max_y, is_one_higher_or_equal),
+            get_adjacents((4, 2), &height_map),
             vec![(3, 2), (5, 2), (4, 1), (4, 3)]
         );
     }
 
     #[test]
-    fn test_bfs() {
-        let (height_map, max_x, max_y, start_coordinate, end_coordinate) = parse_lines(TEST_INPUT);
-        let comparator = |x| x == end_coordinate;
+    fn test_climb() {
+        let (height_map, start_coordinate, end_coordinate) = parse_lines(EXAMPLE_INPUT).unwrap();
         assert_eq!(
-            bfs(
-                height_map.clone(),
-                start_coordinate,
-                max_x,
-                max_y,
-                &comparator,
-                is_one_higher_or_equal
-            ),
+            climb(&height_map, [start_coordinate], end_coordinate).map(|path| path.states),
             Some(vec![
-                Some((0, 0)),
-                Some((1, 0)),
-                Some((2, 0)),
-                Some((2, 1)),
-                Some((2, 2)),
-                Some((2, 3)),
-                Some((2, 4)),
-                Some((3, 4)),
-                Some((4, 4)),
-                Some((5, 4)),
-                Some((6, 4)),
-                Some((7, 4)),
-                Some((7, 3)),
-                Some((7, 2)),
-                Some((7, 1)),
-                Some((7, 0)),
-                Some((6, 0)),
-                Some((5, 0)),
-                Some((4, 0)),
-                Some((3, 0)),
-                Some((3, 1)),
-                Some((3, 2)),
-                Some((3, 3)),
-                Some((4, 3)),
-                Some((5, 3)),
-                Some((6, 3)),
-                Some((6, 2)),
-                Some((6, 1)),
-                Some((5, 1)),
-                Some((4, 1)),
-                Some((4, 2)),
-                Some((5, 2))
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (2, 1),
+                (2, 2),
+                (2, 3),
+                (2, 4),
+                (3, 4),
+                (4, 4),
+                (5, 4),
+                (6, 4),
+                (7, 4),
+                (7, 3),
+                (7, 2),
+                (7, 1),
+                (7, 0),
+                (6, 0),
+                (5, 0),
+                (4, 0),
+                (3, 0),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (4, 3),
+                (5, 3),
+                (6, 3),
+                (6, 2),
+                (6, 1),
+                (5, 1),
+                (4, 1),
+                (4, 2),
+                (5, 2)
             ])
         );
 
-        let two_two_comparator = |x: Coordinate| x == (2, 2);
         assert_eq!(
-            bfs(
-                height_map.clone(),
-                (1, 3),
-                max_x,
-                max_y,
-                &two_two_comparator,
-                is_one_higher_or_equal
-            ),
-            Some(vec![Some((1, 3)), Some((2, 3)), Some((2, 2))])
-        )
+            climb(&height_map, [(1, 3)], (2, 2)).map(|path| path.states),
+            Some(vec![(1, 3), (2, 3), (2, 2)])
+        );
+    }
+
+    // Fewest steps from `start` to the 'E' of `rows`, by a breadth-first search that knows
+    // nothing but the puzzle's climbing rule
+    fn reference_steps(rows: &[Vec<u8>], start: Position) -> Option<usize> {
+        let height = |(x, y): Position| match rows[y][x] {
+            b'S' => b'a',
+            b'E' => b'z',
+            height => height,
+        };
+        let mut steps = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some((x, y)) = queue.pop_front() {
+            if rows[y][x] == b'E' {
+                return Some(steps[&(x, y)]);
+            }
+            let neighbours = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbours {
+                if ny < rows.len()
+                    && nx < rows[ny].len()
+                    && height((nx, ny)) <= height((x, y)) + 1
+                    && !steps.contains_key(&(nx, ny))
+                {
+                    steps.insert((nx, ny), steps[&(x, y)] + 1);
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        None
+    }
+
+    // The generator's maps, from the smallest up to 29 columns by 7 rows
+    fn height_map() -> impl Strategy<Value = String> {
+        (any::<u64>(), 0..30_usize)
+            .prop_map(|(seed, size)| generate_input(&mut Rng::new(seed), size))
+    }
+
+    proptest! {
+        #[test]
+        fn parts_match_reference(input in height_map()) {
+            let rows = input.lines().map(|row| row.as_bytes().to_vec()).collect::<Vec<_>>();
+            let cells = || {
+                rows.iter()
+                    .enumerate()
+                    .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, &c)| ((x, y), c)))
+            };
+            let start = cells().find(|&(_, c)| c == b'S').unwrap().0;
+            // the generator always leaves a way up, like every real input has
+            let expected_part1 = reference_steps(&rows, start);
+            let expected_part2 = cells()
+                .filter(|&(_, c)| c == b'a' || c == b'S')
+                .filter_map(|(coordinate, _)| reference_steps(&rows, coordinate))
+                .min();
+
+            let parsed = parse_lines(&input).unwrap();
+            prop_assert_eq!(Some(part1_impl(&parsed)), expected_part1);
+            prop_assert_eq!(Some(part2_impl(&parsed)), expected_part2);
+        }
     }
 }
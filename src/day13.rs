@@ -1,5 +1,11 @@
-use std::{cmp::Ordering, slice::Iter};
 use itertools::Itertools;
+use std::{cmp::Ordering, fmt, slice::Iter, str::FromStr};
+
+use crate::{
+    generate::Rng,
+    parse_error::{lines, Line},
+    Answer, Example, Params, ParseError, Solution,
+};
 
 #[derive(Debug, PartialEq)]
 enum Token {
@@ -8,118 +14,175 @@ enum Token {
     Comma,
     Digit(u32),
 }
-type Tokens = Vec<Token>;
+// Each token with the text it was read from, for error locations
+type Tokens<'a> = Vec<(Token, &'a str)>;
+
+// Parsing and comparing recurse per nested list, deeper packets are rejected before they can
+// overflow the stack
+const MAX_DEPTH: usize = 256;
 
+// A packet like `[1,[2,[]]]`, the outermost one is always a list
 #[derive(Debug, PartialEq, Clone)]
-enum Packet {
+pub enum Packet {
     List(Vec<Packet>),
     Number(u32),
 }
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Packet::List(packets) => write!(f, "[{}]", packets.iter().join(",")),
+            Packet::Number(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Packet, ParseError> {
+        parse_packet(&Line::new(0, s))
+    }
 }
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u32 = 13;
+    const TITLE: &'static str = "Distress Signal";
+
+    type Parsed = Vec<(Packet, Packet)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(packets: &Self::Parsed, _params: &Params) -> Answer {
+        solve_part1(packets).into()
+    }
+
+    fn part2(packets: &Self::Parsed, _params: &Params) -> Answer {
+        solve_part2(packets).into()
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(13).part2(140)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
-fn parse_line(input: &str) -> Tokens {
-    let mut input_iter = input.chars().peekable();
+fn parse_line<'a>(line: &Line<'a>) -> Result<Tokens<'a>, ParseError> {
+    let text = line.text;
+    let mut chars = text.char_indices().peekable();
     let mut tokens = Tokens::new();
-    loop {
-        let peek = input_iter.peek();
-        match peek {
-            None => break,
-            Some('[') => {
-                input_iter.next();
-                tokens.push(Token::Open)
-            }
-            Some(']') => {
-                input_iter.next();
-                tokens.push(Token::Close)
-            }
-            Some(',') => {
-                input_iter.next();
-                tokens.push(Token::Comma)
-            }
-            Some(x) => {
-                let mut digits = String::new();
-                digits.push(*x);
-                input_iter.next();
-
-                loop {
-                    let next_digit = match input_iter.peek() {
-                        None => break,
-                        Some(c) => *c,
-                    };
-
-                    if !next_digit.is_digit(10) {
-                        break;
-                    }
-
-                    input_iter.next();
-                    digits.push(next_digit);
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            '[' => Token::Open,
+            ']' => Token::Close,
+            ',' => Token::Comma,
+            c if c.is_ascii_digit() => {
+                let mut end = start + 1;
+                while let Some((index, _)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    end = index + 1;
                 }
-                tokens.push(Token::Digit(digits.parse().unwrap()))
+                let digits = &text[start..end];
+                tokens.push((Token::Digit(line.parse(digits)?), digits));
+                continue;
             }
-        }
+            _ => {
+                return Err(line.error(
+                    &text[start..start + c.len_utf8()],
+                    "expected '[', ']', ',' or a digit",
+                ))
+            }
+        };
+        tokens.push((token, &text[start..start + c.len_utf8()]));
     }
-    tokens
+    Ok(tokens)
+}
+
+fn parse_packet(line: &Line) -> Result<Packet, ParseError> {
+    parse_tokens_to_packets(line, &parse_line(line)?)
 }
 
-fn parse_lines(input: &str) -> Vec<(Packet, Packet)> {
-    let mut lines = input.lines().peekable();
+// Pairs of packets on consecutive lines, separated by blank lines
+pub fn parse_lines(input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
+    let mut lines = lines(input).filter(|line| !line.text.is_empty());
     let mut result = vec![];
-    while let Some(_) = lines.peek() {
-        let left = lines.nth(0).unwrap();
-        let right = lines.nth(0).unwrap();
-        result.push((
-            parse_tokens_to_packets(parse_line(left)),
-            parse_tokens_to_packets(parse_line(right)),
-        ));
-        lines.next();
+    while let Some(left) = lines.next() {
+        let right = lines
+            .next()
+            .ok_or_else(|| left.error(left.end(), "expected a second packet on the next line"))?;
+        result.push((parse_packet(&left)?, parse_packet(&right)?));
     }
-    result
+    Ok(result)
+}
+
+fn next_token<'a, 'b>(
+    line: &Line,
+    token_iter: &mut Iter<'b, (Token, &'a str)>,
+) -> Result<&'b (Token, &'a str), ParseError> {
+    token_iter
+        .next()
+        .ok_or_else(|| line.error(line.end(), "unexpected end of packet"))
 }
 
-fn parse_tokens_to_packets_inner(token_iter: &mut Iter<Token>) -> Vec<Packet> {
+fn parse_tokens_to_packets_inner(
+    line: &Line,
+    token_iter: &mut Iter<(Token, &str)>,
+    depth: usize,
+) -> Result<Vec<Packet>, ParseError> {
     let mut elems = vec![];
 
-    let mut current_token = token_iter.next().unwrap();
-    match current_token {
-        Token::Close => elems,
-        _ => {
-            loop {
-                match current_token {
-                    Token::Digit(x) => elems.push(Packet::Number(*x)),
-                    Token::Open => {
-                        elems.push(Packet::List(parse_tokens_to_packets_inner(token_iter)))
-                    }
-                    _ => panic!("unexpected token 1"),
-                }
-                match token_iter.next().unwrap() {
-                    Token::Close => break,
-                    Token::Comma => {
-                        current_token = token_iter.next().unwrap();
-                        continue;
-                    }
-                    token => panic!("unexpected token {:#?}", token),
-                }
+    let mut current_token = next_token(line, token_iter)?;
+    if current_token.0 == Token::Close {
+        return Ok(elems);
+    }
+    loop {
+        match current_token {
+            (Token::Digit(x), _) => elems.push(Packet::Number(*x)),
+            (Token::Open, text) if depth == MAX_DEPTH => {
+                return Err(line.error(
+                    text,
+                    &format!("packets are nested at most {} lists deep", MAX_DEPTH),
+                ))
             }
-            elems
+            (Token::Open, _) => elems.push(Packet::List(parse_tokens_to_packets_inner(
+                line,
+                token_iter,
+                depth + 1,
+            )?)),
+            (_, text) => return Err(line.error(text, "expected a number or '['")),
+        }
+        match next_token(line, token_iter)? {
+            (Token::Close, _) => break,
+            (Token::Comma, _) => current_token = next_token(line, token_iter)?,
+            (_, text) => return Err(line.error(text, "expected ',' or ']'")),
         }
     }
+    Ok(elems)
 }
 
-fn parse_tokens_to_packets(tokens: Tokens) -> Packet {
+fn parse_tokens_to_packets(line: &Line, tokens: &[(Token, &str)]) -> Result<Packet, ParseError> {
     let mut token_iter = tokens.iter();
-    token_iter.next();
+    match token_iter.next() {
+        Some((Token::Open, _)) => {}
+        Some((_, text)) => return Err(line.error(text, "expected '['")),
+        None => return Err(line.error(line.end(), "expected a packet")),
+    }
 
-    Packet::List(parse_tokens_to_packets_inner(&mut token_iter))
+    let packet = Packet::List(parse_tokens_to_packets_inner(line, &mut token_iter, 1)?);
+    match token_iter.next() {
+        Some((_, text)) => Err(line.error(text, "unexpected text after packet")),
+        None => Ok(packet),
+    }
 }
 
-fn compare_packets(left_packet: &Packet, right_packet: &Packet) -> Ordering {
+// The distress signal's packet order, where a number compares like a list holding only it
+pub fn compare_packets(left_packet: &Packet, right_packet: &Packet) -> Ordering {
     match (left_packet, right_packet) {
         (Packet::Number(lx), Packet::Number(rx)) => lx.cmp(rx),
         (Packet::Number(lx), Packet::List(_)) => {
@@ -151,8 +214,8 @@ fn compare_packets(left_packet: &Packet, right_packet: &Packet) -> Ordering {
     }
 }
 
-fn part1_impl(input: &str) -> usize {
-    let packets = parse_lines(input);
+// Sum of the 1-based indices of the pairs that are in the right order
+pub fn solve_part1(packets: &[(Packet, Packet)]) -> usize {
     packets
         .iter()
         .enumerate()
@@ -169,8 +232,9 @@ fn part1_impl(input: &str) -> usize {
         )
 }
 
-fn part2_impl(input: &str) -> usize {
-    let mut packets = parse_lines(input);
+// Product of the positions of the divider packets `[[2]]` and `[[6]]` once all are sorted
+pub fn solve_part2(packets: &[(Packet, Packet)]) -> usize {
+    let mut packets = packets.to_vec();
     packets.push((
         Packet::List(vec![Packet::List(vec![Packet::Number(2)])]),
         Packet::List(vec![Packet::List(vec![Packet::Number(6)])]),
@@ -196,30 +260,62 @@ fn part2_impl(input: &str) -> usize {
         })
 }
 
+fn generate_packet(rng: &mut Rng, depth: usize) -> Packet {
+    Packet::List(
+        (0..rng.below(5))
+            .map(|_| {
+                if depth < 4 && rng.chance(30) {
+                    generate_packet(rng, depth + 1)
+                } else {
+                    Packet::Number(rng.below(11) as u32)
+                }
+            })
+            .collect(),
+    )
+}
+
+// `size` pairs of packets nested up to five lists deep
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{}\n{}", generate_packet(rng, 0), generate_packet(rng, 0)))
+        .join("\n\n")
+}
+
+static EXAMPLE_INPUT: &str = "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4]\n\n[9]\n[[8,7,6]]\n\n[[4,4],4,4]\n[[4,4],4,4,4]\n\n[7,7,7,7]\n[7,7,7]\n\n[]\n[3]\n\n[[[]]]\n[[]]\n\n[1,[2,[3,[4,[5,6,7]]]],8,9]\n[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
 #[cfg(test)]
 mod tests {
     use std::vec;
 
+    use proptest::prelude::*;
+
     use super::*;
 
+    fn unspanned(tokens: Vec<Token>) -> Tokens<'static> {
+        tokens.into_iter().map(|token| (token, "")).collect()
+    }
+
     static TEST_INPUT_SHORT: &str = "[[1],[2,3,4]]\n[[1],4]";
-    static TEST_INPUT: &str = "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4]\n\n[9]\n[[8,7,6]]\n\n[[4,4],4,4]\n[[4,4],4,4,4]\n\n[7,7,7,7]\n[7,7,7]\n\n[]\n[3]\n\n[[[]]]\n[[]]\n\n[1,[2,[3,[4,[5,6,7]]]],8,9]\n[1,[2,[3,[4,[5,6,0]]]],8,9]";
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), 13);
+        assert_eq!(solve_part1(&parse_lines(EXAMPLE_INPUT).unwrap()), 13);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), 140);
+        assert_eq!(solve_part2(&parse_lines(EXAMPLE_INPUT).unwrap()), 140);
     }
 
     #[test]
     fn test_parse_line() {
         assert_eq!(
-            parse_line("[[1],[20,3,4]]"),
-            Tokens::from(vec![
+            parse_line(&Line::new(0, "[[1],[20,3,4]]"))
+                .unwrap()
+                .into_iter()
+                .map(|(token, _)| token)
+                .collect::<Vec<_>>(),
+            vec![
                 Token::Open,
                 Token::Open,
                 Token::Digit(1),
@@ -233,20 +329,27 @@ mod tests {
                 Token::Digit(4),
                 Token::Close,
                 Token::Close,
-            ])
+            ]
         );
     }
 
     #[test]
     fn test_parse_lines() {
         assert_eq!(
-            parse_lines(TEST_INPUT_SHORT),
+            parse_lines(TEST_INPUT_SHORT).unwrap(),
             vec![(
                 Packet::List(vec![
                     Packet::List(vec![Packet::Number(1)]),
-                    Packet::List(vec![Packet::Number(2), Packet::Number(3), Packet::Number(4)])
+                    Packet::List(vec![
+                        Packet::Number(2),
+                        Packet::Number(3),
+                        Packet::Number(4)
+                    ])
                 ]),
-                Packet::List(vec![Packet::List(vec![Packet::Number(1)]), Packet::Number(4)])
+                Packet::List(vec![
+                    Packet::List(vec![Packet::Number(1)]),
+                    Packet::Number(4)
+                ])
             )]
         )
     }
@@ -254,40 +357,64 @@ mod tests {
     #[test]
     fn test_parse_tokens_to_packets() {
         assert_eq!(
-            parse_tokens_to_packets(Tokens::from(vec![
-                Token::Open,
-                Token::Open,
-                Token::Digit(1),
-                Token::Close,
-                Token::Comma,
-                Token::Digit(4),
-                Token::Close
-            ])),
-            Packet::List(vec![Packet::List(vec![Packet::Number(1)]), Packet::Number(4)])
+            parse_tokens_to_packets(
+                &Line::new(0, ""),
+                &unspanned(vec![
+                    Token::Open,
+                    Token::Open,
+                    Token::Digit(1),
+                    Token::Close,
+                    Token::Comma,
+                    Token::Digit(4),
+                    Token::Close
+                ]),
+            )
+            .unwrap(),
+            Packet::List(vec![
+                Packet::List(vec![Packet::Number(1)]),
+                Packet::Number(4)
+            ])
         );
         assert_eq!(
-            parse_tokens_to_packets(Tokens::from(vec![
-                Token::Open,
-                Token::Open,
-                Token::Digit(1),
-                Token::Close,
-                Token::Comma,
-                Token::Open,
-                Token::Digit(2),
-                Token::Comma,
-                Token::Digit(3),
-                Token::Comma,
-                Token::Digit(4),
-                Token::Close,
-                Token::Close,
-            ])),
+            parse_tokens_to_packets(
+                &Line::new(0, ""),
+                &unspanned(vec![
+                    Token::Open,
+                    Token::Open,
+                    Token::Digit(1),
+                    Token::Close,
+                    Token::Comma,
+                    Token::Open,
+                    Token::Digit(2),
+                    Token::Comma,
+                    Token::Digit(3),
+                    Token::Comma,
+                    Token::Digit(4),
+                    Token::Close,
+                    Token::Close,
+                ]),
+            )
+            .unwrap(),
             Packet::List(vec![
                 Packet::List(vec![Packet::Number(1)]),
-                Packet::List(vec![Packet::Number(2), Packet::Number(3), Packet::Number(4)])
+                Packet::List(vec![
+                    Packet::Number(2),
+                    Packet::Number(3),
+                    Packet::Number(4)
+                ])
             ])
         )
     }
 
+    #[test]
+    fn test_parse_lines_error() {
+        let error = parse_lines("[1,[2]\n[3]").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 7));
+
+        let error = parse_lines("[1,2]\n[3,x]").unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 4, "x"));
+    }
+
     #[test]
     fn test_compare_packets() {
         // [1,1,3,1,1] vs [1,1,5,1,1]
@@ -315,7 +442,11 @@ mod tests {
             compare_packets(
                 &Packet::List(vec![
                     Packet::List(vec![Packet::Number(1),]),
-                    Packet::List(vec![Packet::Number(2), Packet::Number(3), Packet::Number(4),]),
+                    Packet::List(vec![
+                        Packet::Number(2),
+                        Packet::Number(3),
+                        Packet::Number(4),
+                    ]),
                 ]),
                 &Packet::List(vec![
                     Packet::List(vec![Packet::Number(1),]),
@@ -362,9 +493,49 @@ mod tests {
                     Packet::Number(7),
                     Packet::Number(7),
                 ]),
-                &Packet::List(vec![Packet::Number(7), Packet::Number(7), Packet::Number(7),]),
+                &Packet::List(vec![
+                    Packet::Number(7),
+                    Packet::Number(7),
+                    Packet::Number(7),
+                ]),
             ),
             Ordering::Greater
         );
     }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "[1,[]]".parse(),
+            Ok(Packet::List(vec![Packet::Number(1), Packet::List(vec![])]))
+        );
+        assert_eq!("[1,]".parse::<Packet>().unwrap_err().column, 4);
+        let deep = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        assert_eq!(deep.parse::<Packet>().unwrap_err().column, MAX_DEPTH + 1);
+    }
+
+    fn packet() -> impl Strategy<Value = Packet> {
+        let element = any::<u32>()
+            .prop_map(Packet::Number)
+            .prop_recursive(4, 32, 5, |element| {
+                prop::collection::vec(element, 0..5).prop_map(Packet::List)
+            });
+        prop::collection::vec(element, 0..5).prop_map(Packet::List)
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trips(packet in packet()) {
+            prop_assert_eq!(packet.to_string().parse(), Ok(packet));
+        }
+
+        #[test]
+        fn compare_packets_is_antisymmetric(left in packet(), right in packet()) {
+            prop_assert_eq!(
+                compare_packets(&right, &left),
+                compare_packets(&left, &right).reverse()
+            );
+            prop_assert_eq!(compare_packets(&left, &left), Ordering::Equal);
+        }
+    }
 }
@@ -1,88 +1,150 @@
-use std::{cmp, collections::HashMap};
+use std::{cmp, convert::TryFrom, fmt};
 
 use itertools::Itertools;
 
-#[derive(Debug, PartialEq)]
-enum Element {
+use crate::{
+    generate::Rng,
+    geometry::{Point, Segment},
+    grid::{Grid, Position},
+    parse_error::{lines, Line},
+    Answer, Example, Params, ParseError, Solution,
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum Element {
+    Air,
     Rock,
     Sand,
 }
 
-type Coordinate = (usize, usize);
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Element::Air => '.',
+            Element::Rock => '#',
+            Element::Sand => 'o',
+        };
+        write!(f, "{}", c)
+    }
+}
 
-type Grid = HashMap<Coordinate, Element>;
+// Far beyond any real scan, keeps a bogus one from making a huge cave
+const MAX_COORDINATE: usize = 1_000;
+const SOURCE: Point = Point::new(500, 0);
+// Straight down, then down to the left, then down to the right
+const FALLS: [Point; 3] = [Point::new(0, 1), Point::new(-1, 1), Point::new(1, 1)];
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input))
+// The scanned rocks down to the floor, two below the lowest rock, and wide enough for sand to
+// pile up on it. `source` is where the sand comes in, at `(500, 0)` of the scan.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Cave {
+    grid: Grid<Element>,
+    source: Position,
 }
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input))
+impl Cave {
+    fn max_y(&self) -> usize {
+        self.grid.height() - 3
+    }
 }
 
-fn get_coordinates_between_points(c1: Coordinate, c2: Coordinate) -> Vec<Coordinate> {
-    let min_x = cmp::min(c1.0, c2.0);
-    let max_x = cmp::max(c1.0, c2.0);
-    let min_y = cmp::min(c1.1, c2.1);
-    let max_y = cmp::max(c1.1, c2.1);
-    (min_x..=max_x)
-        .map(|x| (min_y..=max_y).map(move |y| (x, y)))
-        .flatten()
-        .collect_vec()
+pub(crate) struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u32 = 14;
+    const TITLE: &'static str = "Regolith Reservoir";
+
+    type Parsed = Cave;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(cave: &Self::Parsed, _params: &Params) -> Answer {
+        part1_impl(cave).into()
+    }
+
+    fn part2(cave: &Self::Parsed, _params: &Params) -> Answer {
+        part2_impl(cave).into()
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT).part1(24).part2(93)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
-fn parse_coordinate(s: &str) -> Coordinate {
-    let mut splits = s.split(",");
-    (
-        splits.nth(0).unwrap().parse().unwrap(),
-        splits.nth(0).unwrap().parse().unwrap(),
-    )
+fn parse_coordinate(line: &Line, s: &str) -> Result<Point, ParseError> {
+    let (x, y) = s
+        .split_once(',')
+        .ok_or_else(|| line.error(s, "expected '<x>,<y>'"))?;
+    let coordinate: Position = (line.parse(x)?, line.parse(y)?);
+    if coordinate.0 > MAX_COORDINATE || coordinate.1 > MAX_COORDINATE {
+        return Err(line.error(s, &format!("coordinates are at most {}", MAX_COORDINATE)));
+    }
+    Ok(Point::from(coordinate))
 }
 
-fn parse_lines(input: &str) -> (Grid, usize, usize, usize) {
-    let mut result = Grid::new();
+fn parse_lines(input: &str) -> Result<Cave, ParseError> {
+    let mut rocks = vec![];
     let mut max_y = 0;
-    let mut min_x = 500;
-    let mut max_x = 500;
-    input.lines().for_each(|line| {
-        let mut parts = line.split(" -> ");
-        let mut start_part = parts.next().unwrap();
-        parts.for_each(|part| {
-            get_coordinates_between_points(parse_coordinate(start_part), parse_coordinate(part))
-                .iter()
-                .for_each(|c| {
-                    result.insert(*c, Element::Rock);
-                    max_y = cmp::max(max_y, c.1);
-                    min_x = cmp::min(min_x, c.0);
-                    max_x = cmp::max(max_x, c.0);
-                    start_part = part;
-                })
-        });
-    });
-    (result, max_y, min_x, max_x)
-}
+    let mut min_x = SOURCE.x;
+    let mut max_x = SOURCE.x;
+    for line in lines(input) {
+        let mut points = line.text.split(" -> ");
+        let mut start = parse_coordinate(&line, points.next().unwrap_or(line.text))?;
+        for point in points {
+            let end = parse_coordinate(&line, point)?;
+            if start.x != end.x && start.y != end.y {
+                return Err(line.error(point, "expected a horizontal or vertical line"));
+            }
+            Segment::new(start, end).points().for_each(|rock| {
+                rocks.push(rock);
+                max_y = cmp::max(max_y, rock.y);
+                min_x = cmp::min(min_x, rock.x);
+                max_x = cmp::max(max_x, rock.x);
+            });
+            start = end;
+        }
+    }
 
-fn drop_sand_one_down(sand_coordinate: Coordinate, grid: &Grid) -> Coordinate {
-    let down_coordinate = (sand_coordinate.0, sand_coordinate.1 + 1);
-    let down_left_coordinate = (sand_coordinate.0 - 1, sand_coordinate.1 + 1);
-    let down_right_coordinate = (sand_coordinate.0 + 1, sand_coordinate.1 + 1);
-    match grid.get(&down_coordinate) {
-        Some(_) => match grid.get(&down_left_coordinate) {
-            Some(_) => match grid.get(&down_right_coordinate) {
-                Some(_) => sand_coordinate,
-                None => down_right_coordinate,
-            },
-            None => down_left_coordinate,
-        },
-        None => down_coordinate,
+    // sand piles up at most `max_y + 2` either side of the source, which may be past the rocks
+    let margin = max_y + 2;
+    let mut grid = Grid::new(
+        (max_x - min_x + 2 * margin + 1) as usize,
+        (max_y + 3) as usize,
+        Element::Air,
+    );
+    // the margin left of the leftmost rock or the source becomes the grid's first column
+    let offset = Point::new(margin - min_x, 0);
+    let position = |point: Point| Position::try_from(point + offset).unwrap();
+    for rock in rocks {
+        grid[position(rock)] = Element::Rock;
     }
+    Ok(Cave {
+        grid,
+        source: position(SOURCE),
+    })
+}
+
+fn drop_sand_one_down((x, y): Position, grid: &Grid<Element>) -> Position {
+    // the grid's margin keeps every fall from the source's reach inside it
+    FALLS
+        .iter()
+        .map(|fall| (x.wrapping_add_signed(fall.x), y + fall.y as usize))
+        .find(|&below| grid[below] == Element::Air)
+        .unwrap_or((x, y))
 }
 
-fn part1_impl(input: &str) -> usize {
-    let (mut grid, max_y, _, _) = parse_lines(input);
+fn part1_impl(cave: &Cave) -> usize {
+    let mut grid = cave.grid.clone();
+    let max_y = cave.max_y();
     let mut sand_dropped = 0;
     'outer: loop {
-        let mut sand_coordinate: Coordinate = (500, 0);
+        let mut sand_coordinate = cave.source;
         sand_dropped += 1;
         loop {
             let new_coordinate = drop_sand_one_down(sand_coordinate, &grid);
@@ -94,23 +156,28 @@ fn part1_impl(input: &str) -> usize {
             }
             sand_coordinate = new_coordinate;
         }
-        grid.insert(sand_coordinate, Element::Sand);
+        grid[sand_coordinate] = Element::Sand;
+        // a cave holding the source fills up instead of spilling into the abyss
+        if sand_coordinate == cave.source {
+            return sand_dropped;
+        }
     }
     sand_dropped - 1
 }
 
-fn part2_impl(input: &str) -> usize {
-    let (mut grid, max_y, min_x, max_x) = parse_lines(input);
-    (min_x - max_y..=max_x + max_y).for_each(|x| {
-        grid.insert((x, max_y + 2), Element::Rock);
+fn part2_impl(cave: &Cave) -> usize {
+    let mut grid = cave.grid.clone();
+    let floor = grid.height() - 1;
+    (0..grid.width()).for_each(|x| {
+        grid[(x, floor)] = Element::Rock;
     });
     let mut sand_dropped = 0;
     'outer: loop {
-        let mut sand_coordinate: Coordinate = (500, 0);
+        let mut sand_coordinate = cave.source;
         sand_dropped += 1;
         loop {
             let new_coordinate = drop_sand_one_down(sand_coordinate, &grid);
-            if new_coordinate == (500, 0) {
+            if new_coordinate == cave.source {
                 break 'outer;
             }
             if new_coordinate == sand_coordinate {
@@ -118,140 +185,142 @@ fn part2_impl(input: &str) -> usize {
             }
             sand_coordinate = new_coordinate;
         }
-        grid.insert(sand_coordinate, Element::Sand);
+        grid[sand_coordinate] = Element::Sand;
     }
     sand_dropped
 }
 
+// `size` rock paths of one to four straight lines, spread further down and out for more paths
+// while staying within as many columns either side of the sand's source as there are rows
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let depth = (10 + size as i64 / 2).min(300);
+    let (min_x, max_x) = (500 - depth / 2, 500 + depth / 2);
+    (0..size.max(1))
+        .map(|_| {
+            let mut point = (rng.between(min_x, max_x), rng.between(2, depth));
+            let mut points = vec![point];
+            let horizontal = rng.chance(50);
+            for line in 0..rng.between(1, 4) {
+                let length = rng.between(-8, 8);
+                point = if (line % 2 == 0) == horizontal {
+                    ((point.0 + length).clamp(min_x, max_x), point.1)
+                } else {
+                    (point.0, (point.1 + length).clamp(2, depth))
+                };
+                points.push(point);
+            }
+            points
+                .iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .join(" -> ")
+        })
+        .join("\n")
+}
+
+static EXAMPLE_INPUT: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9";
+
 #[cfg(test)]
 mod tests {
-    use std::vec;
-
     use super::*;
 
-    static TEST_INPUT: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9";
-
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT), 24);
+        assert_eq!(part1_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 24);
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT), 93);
+    fn test_part1_closed_cave() {
+        assert_eq!(
+            part1_impl(&parse_lines("498,0 -> 498,2 -> 502,2 -> 502,0").unwrap()),
+            4
+        );
     }
 
     #[test]
-    fn test_parse_lines() {
-        assert_eq!(
-            parse_lines(TEST_INPUT),
-            (
-                Grid::from([
-                    ((498, 4), Element::Rock),
-                    ((498, 5), Element::Rock),
-                    ((498, 6), Element::Rock),
-                    ((497, 6), Element::Rock),
-                    ((496, 6), Element::Rock),
-                    ((503, 4), Element::Rock),
-                    ((503, 4), Element::Rock),
-                    ((502, 4), Element::Rock),
-                    ((502, 5), Element::Rock),
-                    ((502, 6), Element::Rock),
-                    ((502, 7), Element::Rock),
-                    ((502, 8), Element::Rock),
-                    ((502, 9), Element::Rock),
-                    ((501, 9), Element::Rock),
-                    ((500, 9), Element::Rock),
-                    ((499, 9), Element::Rock),
-                    ((498, 9), Element::Rock),
-                    ((497, 9), Element::Rock),
-                    ((496, 9), Element::Rock),
-                    ((495, 9), Element::Rock),
-                    ((494, 9), Element::Rock),
-                ]),
-                9,
-                494,
-                503
-            )
-        )
+    fn test_part2() {
+        assert_eq!(part2_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 93);
+        // every rock right of the source
+        assert_eq!(part2_impl(&parse_lines("505,1 -> 506,1").unwrap()), 9);
     }
 
-    #[test]
-    fn test_parse_coordinate() {
-        assert_eq!(parse_coordinate("498,4"), (498, 4))
+    // The part of the example's cave the puzzle draws, x from 494 to 503 and y from 0 to 9
+    fn example_scan(grid: &Grid<Element>, source: Position) -> String {
+        let left = source.0 - (SOURCE.x as usize - 494);
+        grid.rows()
+            .take(10)
+            .map(|row| row[left..left + 10].iter().join(""))
+            .join("\n")
     }
 
     #[test]
-    fn test_get_coordinates_between_points() {
-        assert_eq!(
-            get_coordinates_between_points((498, 4), (498, 6)),
-            vec![(498, 4), (498, 5), (498, 6)]
-        );
-        assert_eq!(
-            get_coordinates_between_points((503, 4), (502, 4)),
-            vec![(502, 4), (503, 4)]
-        );
+    fn test_parse_lines() {
+        let cave = parse_lines(EXAMPLE_INPUT).unwrap();
+        assert_eq!((cave.grid.width(), cave.grid.height()), (32, 12));
+        assert_eq!(cave.source, (17, 0));
         assert_eq!(
-            get_coordinates_between_points((502, 4), (502, 9)),
-            vec![(502, 4), (502, 5), (502, 6), (502, 7), (502, 8), (502, 9)]
+            example_scan(&cave.grid, cave.source),
+            "..........\n\
+             ..........\n\
+             ..........\n\
+             ..........\n\
+             ....#...##\n\
+             ....#...#.\n\
+             ..###...#.\n\
+             ........#.\n\
+             ........#.\n\
+             #########."
         );
+        assert_eq!(parse_lines("498,4 -> 500,6").unwrap_err().column, 10);
+    }
+
+    #[test]
+    fn test_parse_coordinate() {
+        let line = Line::new(0, "498,4");
+        assert_eq!(parse_coordinate(&line, line.text), Ok(Point::new(498, 4)));
+        let line = Line::new(0, "498;4");
+        assert_eq!(parse_coordinate(&line, line.text).unwrap_err().column, 1);
+        let line = Line::new(0, "498,40000");
+        assert!(parse_coordinate(&line, line.text).is_err());
     }
 
     #[test]
     fn test_drop_sand_one_down() {
-        let (mut grid, _, _, _) = parse_lines(TEST_INPUT);
-        assert_eq!(drop_sand_one_down((500, 0), &grid), (500, 1));
-        assert_eq!(drop_sand_one_down((500, 1), &grid), (500, 2));
-        assert_eq!(drop_sand_one_down((500, 2), &grid), (500, 3));
-        assert_eq!(drop_sand_one_down((500, 3), &grid), (500, 4));
-        assert_eq!(drop_sand_one_down((500, 4), &grid), (500, 5));
-        assert_eq!(drop_sand_one_down((500, 5), &grid), (500, 6));
-        assert_eq!(drop_sand_one_down((500, 6), &grid), (500, 7));
-        assert_eq!(drop_sand_one_down((500, 7), &grid), (500, 8));
-        assert_eq!(drop_sand_one_down((500, 8), &grid), (500, 8));
-        grid.insert((500, 8), Element::Sand);
-        assert_eq!(drop_sand_one_down((500, 7), &grid), (499, 8));
-        assert_eq!(drop_sand_one_down((499, 8), &grid), (499, 8));
-        grid.insert((499, 8), Element::Sand);
-        assert_eq!(drop_sand_one_down((500, 7), &grid), (501, 8));
-        assert_eq!(drop_sand_one_down((501, 8), &grid), (501, 8));
-        grid.insert((501, 8), Element::Sand);
-        assert_eq!(drop_sand_one_down((500, 7), &grid), (500, 7));
-        grid.insert((500, 7), Element::Sand);
-        assert_eq!(drop_sand_one_down((500, 6), &grid), (499, 7));
-        assert_eq!(drop_sand_one_down((499, 7), &grid), (498, 8));
-        assert_eq!(drop_sand_one_down((498, 8), &grid), (498, 8));
-        grid.insert((498, 8), Element::Sand);
+        let Cave { mut grid, source } = parse_lines(EXAMPLE_INPUT).unwrap();
+        let at = |x: usize, y: usize| (x + source.0 - SOURCE.x as usize, y);
+        assert_eq!(drop_sand_one_down(at(500, 0), &grid), at(500, 1));
+        assert_eq!(drop_sand_one_down(at(500, 1), &grid), at(500, 2));
+        assert_eq!(drop_sand_one_down(at(500, 2), &grid), at(500, 3));
+        assert_eq!(drop_sand_one_down(at(500, 3), &grid), at(500, 4));
+        assert_eq!(drop_sand_one_down(at(500, 4), &grid), at(500, 5));
+        assert_eq!(drop_sand_one_down(at(500, 5), &grid), at(500, 6));
+        assert_eq!(drop_sand_one_down(at(500, 6), &grid), at(500, 7));
+        assert_eq!(drop_sand_one_down(at(500, 7), &grid), at(500, 8));
+        assert_eq!(drop_sand_one_down(at(500, 8), &grid), at(500, 8));
+        grid[at(500, 8)] = Element::Sand;
+        assert_eq!(drop_sand_one_down(at(500, 7), &grid), at(499, 8));
+        assert_eq!(drop_sand_one_down(at(499, 8), &grid), at(499, 8));
+        grid[at(499, 8)] = Element::Sand;
+        assert_eq!(drop_sand_one_down(at(500, 7), &grid), at(501, 8));
+        assert_eq!(drop_sand_one_down(at(501, 8), &grid), at(501, 8));
+        grid[at(501, 8)] = Element::Sand;
+        assert_eq!(drop_sand_one_down(at(500, 7), &grid), at(500, 7));
+        grid[at(500, 7)] = Element::Sand;
+        assert_eq!(drop_sand_one_down(at(500, 6), &grid), at(499, 7));
+        assert_eq!(drop_sand_one_down(at(499, 7), &grid), at(498, 8));
+        assert_eq!(drop_sand_one_down(at(498, 8), &grid), at(498, 8));
+        grid[at(498, 8)] = Element::Sand;
         assert_eq!(
-            grid,
-            Grid::from([
-                ((498, 4), Element::Rock),
-                ((498, 5), Element::Rock),
-                ((498, 6), Element::Rock),
-                ((497, 6), Element::Rock),
-                ((496, 6), Element::Rock),
-                ((503, 4), Element::Rock),
-                ((503, 4), Element::Rock),
-                ((502, 4), Element::Rock),
-                ((502, 5), Element::Rock),
-                ((502, 6), Element::Rock),
-                ((502, 7), Element::Rock),
-                ((502, 8), Element::Rock),
-                ((502, 9), Element::Rock),
-                ((501, 9), Element::Rock),
-                ((500, 9), Element::Rock),
-                ((499, 9), Element::Rock),
-                ((498, 9), Element::Rock),
-                ((497, 9), Element::Rock),
-                ((496, 9), Element::Rock),
-                ((495, 9), Element::Rock),
-                ((494, 9), Element::Rock),
-                ((500, 8), Element::Sand),
-                ((499, 8), Element::Sand),
-                ((501, 8), Element::Sand),
-                ((500, 7), Element::Sand),
-                ((498, 8), Element::Sand),
-            ])
+            example_scan(&grid, source),
+            "..........\n\
+             ..........\n\
+             ..........\n\
+             ..........\n\
+             ....#...##\n\
+             ....#...#.\n\
+             ..###...#.\n\
+             ......o.#.\n\
+             ....oooo#.\n\
+             #########."
         );
     }
 }
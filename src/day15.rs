@@ -1,242 +1,342 @@
-use std::{
-    cmp,
-    collections::{HashMap, HashSet},
-    hash::Hash,
-    vec,
-};
+use std::{cmp, str::FromStr, vec};
 
 use itertools::Itertools;
-use num::abs;
 
-type Coordinate = (isize, isize);
+use crate::{
+    generate::Rng,
+    geometry::{Point, Segment},
+    parse_error::{lines, Line},
+    Answer, Example, Param, Params, ParseError, Solution,
+};
 
+// A sensor and the closest beacon it picked up, like
+// `Sensor at x=2, y=18: closest beacon is at x=-2, y=15`
 #[derive(Debug, PartialEq, Copy, Clone)]
-struct Sensor {
-    beacon_position: Coordinate,
-    position: Coordinate,
+pub struct Sensor {
+    pub beacon_position: Point,
+    pub position: Point,
 }
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input, 2000000))
+impl Sensor {
+    // How far the sensor covers, every position closer than its beacon cannot hold a beacon
+    pub fn reach(&self) -> isize {
+        self.position.manhattan(self.beacon_position)
+    }
 }
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input, 4000000, 4000000))
-}
+impl FromStr for Sensor {
+    type Err = ParseError;
 
-fn parse_coordinate(s: &str) -> Coordinate {
-    let mut splits = s.split(", y=");
-    let x_part = splits.nth(0).unwrap().trim_start_matches("x=");
-    let y_part = splits.nth(0).unwrap();
-    (x_part.parse().unwrap(), y_part.parse().unwrap())
+    fn from_str(s: &str) -> Result<Sensor, ParseError> {
+        parse_sensor(&Line::new(0, s))
+    }
 }
 
-fn parse_lines(input: &str) -> Vec<Sensor> {
-    input
-        .lines()
-        .map(|line| {
-            let mut splits = line.split(": closest beacon is at ");
-            let sensor_part = splits.nth(0).unwrap().trim_start_matches("Sensor at ");
-            let beacon_part = splits.nth(0).unwrap();
-            Sensor {
-                beacon_position: parse_coordinate(beacon_part),
-                position: parse_coordinate(sensor_part),
-            }
-        })
-        .collect_vec()
-}
+pub struct Day15;
 
-fn get_manhattan_distance(c1: Coordinate, c2: Coordinate) -> isize {
-    abs(c1.0 - c2.0) + abs(c1.1 - c2.1)
-}
+impl Solution for Day15 {
+    const DAY: u32 = 15;
+    const TITLE: &'static str = "Beacon Exclusion Zone";
 
-fn is_in_range(c: Coordinate, sensor: &Sensor) -> bool {
-    get_manhattan_distance(c, sensor.position)
-        <= get_manhattan_distance(sensor.position, sensor.beacon_position)
-}
+    // part 2 searches x and y from 0 up to `max_coordinate`
+    const PARAMS: &'static [Param] = &[
+        Param::new("row", 2000000),
+        Param::new("max_coordinate", 4000000).min(0),
+    ];
 
-fn calculate_intersection(
-    c11: Coordinate,
-    c12: Coordinate,
-    c21: Coordinate,
-    c22: Coordinate,
-) -> Option<Coordinate> {
-    let a1 = (c12.1 - c11.1) as i128;
-    let b1 = (c11.0 - c12.0) as i128;
-    let c1 = a1 * c11.0 as i128 + b1 * c11.1 as i128;
+    type Parsed = Vec<Sensor>;
 
-    let a2 = (c22.1 - c21.1) as i128;
-    let b2 = (c21.0 - c22.0) as i128;
-    let c2 = a2 * c21.0 as i128 + b2 * c21.1 as i128;
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
 
-    let delta = a1 * b2 - a2 * b1;
+    fn part1(sensors: &Self::Parsed, params: &Params) -> Answer {
+        solve_part1(sensors, params.get("row")).into()
+    }
 
-    if delta == 0 {
-        return None;
+    fn part2(sensors: &Self::Parsed, params: &Params) -> Answer {
+        let max_coordinate = params.get("max_coordinate");
+        solve_part2(sensors, max_coordinate, max_coordinate).into()
     }
 
-    Some((
-        ((b2 * c1 - b1 * c2) / delta) as isize,
-        ((a1 * c2 - a2 * c1) / delta) as isize,
-    ))
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT)
+            .param("row", 10)
+            .param("max_coordinate", 20)
+            .part1(26)
+            .part2(56000011)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, params: &Params) -> Option<String> {
+        Some(generate_input(rng, size, params.get("max_coordinate")))
+    }
+}
+
+fn parse_coordinate(line: &Line, s: &str) -> Result<Point, ParseError> {
+    let (x, y) = s
+        .strip_prefix("x=")
+        .and_then(|s| s.split_once(", y="))
+        .ok_or_else(|| line.error(s, "expected 'x=<x>, y=<y>'"))?;
+    Ok(Point::new(line.parse(x)?, line.parse(y)?))
+}
+
+fn parse_sensor(line: &Line) -> Result<Sensor, ParseError> {
+    let (sensor_part, beacon_part) = line
+        .strip_prefix("Sensor at ")?
+        .split_once(": closest beacon is at ")
+        .ok_or_else(|| line.error(line.end(), "expected ': closest beacon is at'"))?;
+    Ok(Sensor {
+        beacon_position: parse_coordinate(line, beacon_part)?,
+        position: parse_coordinate(line, sensor_part)?,
+    })
+}
+
+// One sensor per line
+pub fn parse_lines(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    lines(input).map(|line| parse_sensor(&line)).collect()
+}
+
+fn is_in_range(c: Point, sensor: &Sensor) -> bool {
+    sensor.position.manhattan(c) <= sensor.reach()
 }
 
-fn part1_impl(input: &str, y: isize) -> usize {
-    let sensors = parse_lines(input);
+// Number of positions in row `y` that cannot hold a beacon
+pub fn solve_part1(sensors: &[Sensor], y: isize) -> usize {
     let max_x = sensors.iter().fold(0, |acc, sensor| {
-        cmp::max(acc, cmp::max(sensor.position.0, sensor.beacon_position.0))
+        cmp::max(acc, cmp::max(sensor.position.x, sensor.beacon_position.x))
     });
     let min_x = sensors.iter().fold(0, |acc, sensor| {
-        cmp::min(acc, cmp::min(sensor.position.0, sensor.beacon_position.0))
-    });
-    let farthest_ditance = sensors.iter().fold(0 as isize, |acc, sensor| {
-        cmp::max(
-            acc,
-            get_manhattan_distance(sensor.position, sensor.beacon_position),
-        )
+        cmp::min(acc, cmp::min(sensor.position.x, sensor.beacon_position.x))
     });
+    let farthest_ditance = sensors
+        .iter()
+        .fold(0_isize, |acc, sensor| cmp::max(acc, sensor.reach()));
     let positions_reachable =
         (min_x - farthest_ditance..=max_x + farthest_ditance).fold(vec![], |mut acc, test_x| {
-            match sensors
-                .iter()
-                .find(|&sensor| is_in_range((test_x, y), sensor))
-            {
-                Some(_) => acc.push((test_x, y)),
-                None => (),
+            let position = Point::new(test_x, y);
+            if sensors.iter().any(|sensor| is_in_range(position, sensor)) {
+                acc.push(position)
             }
             acc
         });
     let filtered_positions_reachable = positions_reachable
         .iter()
-        .filter(|&c| {
-            sensors
-                .iter()
-                .find(|sensor| sensor.position == *c || sensor.beacon_position == *c)
-                == None
-        })
+        .filter(|&c| sensors.iter().all(|sensor| sensor.beacon_position != *c))
         .collect_vec();
     filtered_positions_reachable.len()
 }
 
-fn part2_impl(input: &str, max_x: isize, max_y: isize) -> isize {
-    let sensors = parse_lines(input);
-    let min_x = 0;
-    let min_y = 0;
+// First position within 0..=max_x and 0..=max_y no sensor covers, found by merging the ranges
+// each sensor covers in every row. Much slower than intersecting the sensors' edges, but also
+// finds gaps those lines miss, like ones against the edge of the search area.
+fn scan_rows(sensors: &[Sensor], max_x: isize, max_y: isize) -> Option<Point> {
+    (0..=max_y).find_map(|y| {
+        let ranges = sensors
+            .iter()
+            .filter_map(|sensor| {
+                let reach = sensor.reach() - (sensor.position.y - y).abs();
+                (reach >= 0).then(|| (sensor.position.x - reach, sensor.position.x + reach))
+            })
+            .sorted();
+        let mut x = 0;
+        for (from, to) in ranges {
+            if from > x {
+                break;
+            }
+            x = cmp::max(x, to + 1);
+        }
+        (x <= max_x).then_some(Point::new(x, y))
+    })
+}
+
+// The four sides of the diamond just outside what `sensor` covers
+fn edges(sensor: &Sensor) -> [Segment; 4] {
+    let distance = sensor.reach() + 1;
+    let north = sensor.position + Point::new(0, distance);
+    let east = sensor.position + Point::new(distance, 0);
+    let south = sensor.position - Point::new(0, distance);
+    let west = sensor.position - Point::new(distance, 0);
+    [
+        Segment::new(north, east),
+        Segment::new(east, south),
+        Segment::new(south, west),
+        Segment::new(west, north),
+    ]
+}
+
+// Tuning frequency of the only position within 0..=max_x and 0..=max_y no sensor covers,
+// `None` when the sensors cover every one
+pub fn solve_part2(sensors: &[Sensor], max_x: isize, max_y: isize) -> Option<isize> {
     let beacon_point = sensors
+        .iter()
+        .flat_map(edges)
+        .tuple_combinations()
+        .filter_map(|(edge1, edge2)| edge1.intersection(edge2))
+        .filter(|point| 0 <= point.x && point.x <= max_x && 0 <= point.y && point.y <= max_y)
+        .find(|&point| sensors.iter().all(|s| !is_in_range(point, s)))
+        .or_else(|| scan_rows(sensors, max_x, max_y))?;
+    Some(beacon_point.x * 4_000_000 + beacon_point.y)
+}
+
+// A sensor whose beacon is `reach` away from it, in a random direction
+fn generate_sensor(rng: &mut Rng, position: Point, reach: isize) -> Sensor {
+    let dx = rng.between(-(reach as i64), reach as i64) as isize;
+    let dy = (reach - dx.abs()) * if rng.chance(50) { 1 } else { -1 };
+    Sensor {
+        beacon_position: position + Point::new(dx, dy),
+        position,
+    }
+}
+
+// `size` sensors, at least four, covering all of the square from 0 to `max_coordinate` but one
+// position. Four sensors diagonally around the gap, each reaching right up to it, cover the
+// square on their own; the others are scattered over it, each stopping short of the gap, and
+// there are no more of them than positions in the square besides the gap.
+fn generate_input(rng: &mut Rng, size: usize, max_coordinate: isize) -> String {
+    let max = max_coordinate as i64;
+    let random_point =
+        |rng: &mut Rng| Point::new(rng.between(0, max) as isize, rng.between(0, max) as isize);
+    let gap = random_point(rng);
+    let distance = max_coordinate.max(1);
+    let mut sensors = [
+        Point::new(1, 1),
+        Point::new(1, -1),
+        Point::new(-1, 1),
+        Point::new(-1, -1),
+    ]
+    .iter()
+    .map(|&diagonal| {
+        let position = gap + diagonal * distance;
+        generate_sensor(rng, position, 2 * distance - 1)
+    })
+    .collect_vec();
+    let free = (max_coordinate as usize + 1)
+        .checked_pow(2)
+        .map_or(usize::MAX, |positions| positions - 1);
+    let scattered = (size.max(4) - 4).min(free);
+    while sensors.len() < 4 + scattered {
+        let position = random_point(rng);
+        if position != gap {
+            let reach = rng.between(0, position.manhattan(gap) as i64 - 1);
+            sensors.push(generate_sensor(rng, position, reach as isize));
+        }
+    }
+    rng.shuffle(&mut sensors);
+    sensors
         .iter()
         .map(|sensor| {
-            let manhattan_distance_extra =
-                get_manhattan_distance(sensor.position, sensor.beacon_position) + 1;
-            let north = (
-                sensor.position.0,
-                sensor.position.1 + manhattan_distance_extra,
-            );
-            let east = (
-                sensor.position.0 + manhattan_distance_extra,
-                sensor.position.1,
-            );
-            let south = (
-                sensor.position.0,
-                sensor.position.1 - manhattan_distance_extra,
-            );
-            let west = (
-                sensor.position.0 - manhattan_distance_extra,
-                sensor.position.1,
-            );
-            [(north, east), (east, south), (south, west), (west, north)]
-        })
-        .flatten()
-        .combinations(2)
-        .filter_map(|lines| calculate_intersection(lines[0].0, lines[0].1, lines[1].0, lines[1].1))
-        .filter(|(x, y)| 0 <= *x && *x <= max_x && 0 <= *y && *y <= max_y)
-        .find(|(x, y)| {
-            sensors.iter().all(|s| {
-                get_manhattan_distance(s.position, s.beacon_position)
-                    < get_manhattan_distance(s.position, (*x, *y))
-            })
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                sensor.position.x,
+                sensor.position.y,
+                sensor.beacon_position.x,
+                sensor.beacon_position.y
+            )
         })
-        .unwrap();
-    beacon_point.0 * 4_000_000 + beacon_point.1
+        .join("\n")
 }
 
+static EXAMPLE_INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9, y=16: closest beacon is at x=10, y=16\nSensor at x=13, y=2: closest beacon is at x=15, y=3\nSensor at x=12, y=14: closest beacon is at x=10, y=16\nSensor at x=10, y=20: closest beacon is at x=10, y=16\nSensor at x=14, y=17: closest beacon is at x=10, y=16\nSensor at x=8, y=7: closest beacon is at x=2, y=10\nSensor at x=2, y=0: closest beacon is at x=2, y=10\nSensor at x=0, y=11: closest beacon is at x=2, y=10\nSensor at x=20, y=14: closest beacon is at x=25, y=17\nSensor at x=17, y=20: closest beacon is at x=21, y=22\nSensor at x=16, y=7: closest beacon is at x=15, y=3\nSensor at x=14, y=3: closest beacon is at x=15, y=3\nSensor at x=20, y=1: closest beacon is at x=15, y=3";
+
 #[cfg(test)]
 mod tests {
     use std::vec;
 
-    use super::*;
+    use proptest::prelude::*;
 
-    static TEST_INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9, y=16: closest beacon is at x=10, y=16\nSensor at x=13, y=2: closest beacon is at x=15, y=3\nSensor at x=12, y=14: closest beacon is at x=10, y=16\nSensor at x=10, y=20: closest beacon is at x=10, y=16\nSensor at x=14, y=17: closest beacon is at x=10, y=16\nSensor at x=8, y=7: closest beacon is at x=2, y=10\nSensor at x=2, y=0: closest beacon is at x=2, y=10\nSensor at x=0, y=11: closest beacon is at x=2, y=10\nSensor at x=20, y=14: closest beacon is at x=25, y=17\nSensor at x=17, y=20: closest beacon is at x=21, y=22\nSensor at x=16, y=7: closest beacon is at x=15, y=3\nSensor at x=14, y=3: closest beacon is at x=15, y=3\nSensor at x=20, y=1: closest beacon is at x=15, y=3";
+    use super::*;
+    use crate::generate::Rng;
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT, 10), 26);
+        assert_eq!(solve_part1(&parse_lines(EXAMPLE_INPUT).unwrap(), 10), 26);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT, 20, 20), 56000011);
+        assert_eq!(
+            solve_part2(&parse_lines(EXAMPLE_INPUT).unwrap(), 20, 20),
+            Some(56000011)
+        );
+        // a single sensor reaching past every corner of the square
+        let covered = parse_lines("Sensor at x=1, y=1: closest beacon is at x=10, y=10").unwrap();
+        assert_eq!(solve_part2(&covered, 3, 3), None);
+    }
+
+    #[test]
+    fn test_generate_input() {
+        // a square of a single position has no room for sensors besides the four around it
+        for (max_coordinate, size, sensors) in [(0, 5, 4), (1, 50, 7), (20, 50, 50)] {
+            let input = generate_input(&mut Rng::new(1), size, max_coordinate);
+            let parsed = parse_lines(&input).unwrap();
+            assert_eq!(parsed.len(), sensors);
+            let frequency = solve_part2(&parsed, max_coordinate, max_coordinate).unwrap();
+            assert!(
+                frequency / 4_000_000 <= max_coordinate && frequency % 4_000_000 <= max_coordinate
+            );
+        }
     }
 
     #[test]
     fn test_parse_lines() {
         assert_eq!(
-            parse_lines(TEST_INPUT),
+            parse_lines(EXAMPLE_INPUT).unwrap(),
             vec![
                 Sensor {
-                    beacon_position: (-2, 15),
-                    position: (2, 18)
+                    beacon_position: Point::new(-2, 15),
+                    position: Point::new(2, 18)
                 },
                 Sensor {
-                    beacon_position: (10, 16),
-                    position: (9, 16)
+                    beacon_position: Point::new(10, 16),
+                    position: Point::new(9, 16)
                 },
                 Sensor {
-                    beacon_position: (15, 3),
-                    position: (13, 2)
+                    beacon_position: Point::new(15, 3),
+                    position: Point::new(13, 2)
                 },
                 Sensor {
-                    beacon_position: (10, 16),
-                    position: (12, 14)
+                    beacon_position: Point::new(10, 16),
+                    position: Point::new(12, 14)
                 },
                 Sensor {
-                    beacon_position: (10, 16),
-                    position: (10, 20)
+                    beacon_position: Point::new(10, 16),
+                    position: Point::new(10, 20)
                 },
                 Sensor {
-                    beacon_position: (10, 16),
-                    position: (14, 17)
+                    beacon_position: Point::new(10, 16),
+                    position: Point::new(14, 17)
                 },
                 Sensor {
-                    beacon_position: (2, 10),
-                    position: (8, 7)
+                    beacon_position: Point::new(2, 10),
+                    position: Point::new(8, 7)
                 },
                 Sensor {
-                    beacon_position: (2, 10),
-                    position: (2, 0)
+                    beacon_position: Point::new(2, 10),
+                    position: Point::new(2, 0)
                 },
                 Sensor {
-                    beacon_position: (2, 10),
-                    position: (0, 11)
+                    beacon_position: Point::new(2, 10),
+                    position: Point::new(0, 11)
                 },
                 Sensor {
-                    beacon_position: (25, 17),
-                    position: (20, 14)
+                    beacon_position: Point::new(25, 17),
+                    position: Point::new(20, 14)
                 },
                 Sensor {
-                    beacon_position: (21, 22),
-                    position: (17, 20)
+                    beacon_position: Point::new(21, 22),
+                    position: Point::new(17, 20)
                 },
                 Sensor {
-                    beacon_position: (15, 3),
-                    position: (16, 7)
+                    beacon_position: Point::new(15, 3),
+                    position: Point::new(16, 7)
                 },
                 Sensor {
-                    beacon_position: (15, 3),
-                    position: (14, 3)
+                    beacon_position: Point::new(15, 3),
+                    position: Point::new(14, 3)
                 },
                 Sensor {
-                    beacon_position: (15, 3),
-                    position: (20, 1)
+                    beacon_position: Point::new(15, 3),
+                    position: Point::new(20, 1)
                 }
             ]
         )
@@ -244,55 +344,148 @@ mod tests {
 
     #[test]
     fn test_parse_coordinate() {
-        assert_eq!(parse_coordinate("x=-2, y=18"), (-2, 18))
+        let line = Line::new(0, "x=-2, y=18");
+        assert_eq!(parse_coordinate(&line, line.text), Ok(Point::new(-2, 18)))
     }
 
     #[test]
-    fn test_get_manhattan_distance() {
-        assert_eq!(get_manhattan_distance((0, 0), (5, 10)), 15);
-        assert_eq!(get_manhattan_distance((23, 6), (17, 9)), 9);
+    fn test_is_in_range() {
+        assert!(!is_in_range(
+            Point::new(0, 0),
+            &Sensor {
+                beacon_position: Point::new(2, 10),
+                position: Point::new(8, 7)
+            }
+        ));
+        assert!(is_in_range(
+            Point::new(2, 10),
+            &Sensor {
+                beacon_position: Point::new(2, 10),
+                position: Point::new(8, 7)
+            }
+        ));
+        assert!(!is_in_range(
+            Point::new(1, 10),
+            &Sensor {
+                beacon_position: Point::new(2, 10),
+                position: Point::new(8, 7)
+            }
+        ));
     }
 
     #[test]
-    fn test_is_in_range() {
-        assert_eq!(
-            is_in_range(
-                (0, 0),
-                &Sensor {
-                    beacon_position: (2, 10),
-                    position: (8, 7)
-                }
-            ),
-            false
-        );
+    fn test_from_str() {
         assert_eq!(
-            is_in_range(
-                (2, 10),
-                &Sensor {
-                    beacon_position: (2, 10),
-                    position: (8, 7)
-                }
-            ),
-            true
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=15".parse(),
+            Ok(Sensor {
+                beacon_position: Point::new(-2, 15),
+                position: Point::new(2, 18),
+            })
         );
-        assert_eq!(
-            is_in_range(
-                (1, 10),
-                &Sensor {
-                    beacon_position: (2, 10),
-                    position: (8, 7)
+        assert!("Sensor at x=2, y=18".parse::<Sensor>().is_err());
+    }
+
+    fn covered(sensors: &[Sensor], position: Point) -> bool {
+        sensors.iter().any(|sensor| {
+            sensor.position.manhattan(position) <= sensor.position.manhattan(sensor.beacon_position)
+        })
+    }
+
+    // Positions of row `y` some sensor covers, other than the beacons themselves, checked one
+    // by one over every x any sensor could reach
+    fn reference_part1(sensors: &[Sensor], y: isize) -> usize {
+        let reach = |sensor: &Sensor| sensor.position.manhattan(sensor.beacon_position);
+        let min_x = sensors
+            .iter()
+            .map(|s| s.position.x - reach(s))
+            .min()
+            .unwrap_or(0);
+        let max_x = sensors
+            .iter()
+            .map(|s| s.position.x + reach(s))
+            .max()
+            .unwrap_or(0);
+        (min_x..=max_x)
+            .filter(|&x| {
+                let position = Point::new(x, y);
+                covered(sensors, position) && sensors.iter().all(|s| s.beacon_position != position)
+            })
+            .count()
+    }
+
+    // Tuning frequency of the first uncovered position in the square, trying all of them
+    fn reference_part2(sensors: &[Sensor], max_coordinate: isize) -> Option<isize> {
+        (0..=max_coordinate)
+            .flat_map(|x| (0..=max_coordinate).map(move |y| Point::new(x, y)))
+            .find(|&position| !covered(sensors, position))
+            .map(|position| position.x * 4_000_000 + position.y)
+    }
+
+    // A sensor at `position` reaching as far as it can without covering `gap`
+    fn sensor_short_of(position: Point, gap: Point) -> Sensor {
+        let reach = position.manhattan(gap) - 1;
+        Sensor {
+            beacon_position: position + Point::new(reach, 0),
+            position,
+        }
+    }
+
+    fn coordinate(max_coordinate: isize) -> impl Strategy<Value = Point> {
+        (0..=max_coordinate, 0..=max_coordinate).prop_map(Point::from)
+    }
+
+    // Sensors with nearby beacons scattered over a small area
+    fn sensors() -> impl Strategy<Value = Vec<Sensor>> {
+        prop::collection::vec(
+            (coordinate(30), -5..=5_isize, -5..=5_isize).prop_map(|(position, dx, dy)| Sensor {
+                beacon_position: position + Point::new(dx, dy),
+                position,
+            }),
+            0..8,
+        )
+    }
+
+    // Sensors covering all of the square from 0 to `max_coordinate` but a single gap, like the
+    // real input: some placed at random, then one at each position still uncovered
+    fn sensors_with_gap() -> impl Strategy<Value = (Vec<Sensor>, isize)> {
+        (1..=20_isize)
+            .prop_flat_map(|max_coordinate| {
+                (
+                    Just(max_coordinate),
+                    coordinate(max_coordinate),
+                    prop::collection::vec(coordinate(max_coordinate), 0..6),
+                )
+            })
+            .prop_map(|(max_coordinate, gap, positions)| {
+                let mut sensors = positions
+                    .into_iter()
+                    .filter(|&position| position != gap)
+                    .map(|position| sensor_short_of(position, gap))
+                    .collect::<Vec<_>>();
+                for x in 0..=max_coordinate {
+                    for y in 0..=max_coordinate {
+                        let position = Point::new(x, y);
+                        if position != gap && !covered(&sensors, position) {
+                            sensors.push(sensor_short_of(position, gap));
+                        }
+                    }
                 }
-            ),
-            false
-        );
+                (sensors, max_coordinate)
+            })
     }
 
-    #[test]
-    fn test_calculate_intersection() {
-        assert_eq!(
-            calculate_intersection((4, 0), (6, 10), (0, 3), (10, 7)),
-            Some((5, 5))
-        );
-        assert_eq!(calculate_intersection((0, 0), (1, 1), (1, 2), (4, 5)), None)
+    proptest! {
+        #[test]
+        fn part1_matches_reference(sensors in sensors(), y in -5..35_isize) {
+            prop_assert_eq!(solve_part1(&sensors, y), reference_part1(&sensors, y));
+        }
+
+        #[test]
+        fn part2_matches_reference((sensors, max_coordinate) in sensors_with_gap()) {
+            prop_assert_eq!(
+                solve_part2(&sensors, max_coordinate, max_coordinate),
+                reference_part2(&sensors, max_coordinate)
+            );
+        }
     }
 }
@@ -1,125 +1,137 @@
-use std::{cmp, collections::HashMap};
+use crate::{
+    generate::Rng,
+    parse_error::{lines, Line},
+    Example, Params, ParseError, Solution,
+};
 
-use itertools::Itertools;
-use num::abs;
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-struct Valve {
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Valve {
+    name: String,
     flow_rate: usize,
-    tunnels: Vec<&str>,
+    tunnels: Vec<String>,
 }
 
-pub fn part1(input: String) {
-    println!("{}", part1_impl(&input, 2000000))
-}
+pub(crate) struct Day16;
 
-pub fn part2(input: String) {
-    println!("{}", part2_impl(&input, 4000000, 4000000))
-}
+impl Solution for Day16 {
+    const DAY: u32 = 16;
+    const TITLE: &'static str = "Proboscidea Volcanium";
+    const PARTS: u32 = 0;
 
-fn parse_lines(input: &str) -> Vec<Sensor> {
-    input
-        .lines()
-        .map(|line| {
-            let mut splits = line.split(": closest beacon is at ");
-            let sensor_part = splits.nth(0).unwrap().trim_start_matches("Sensor at ");
-            let beacon_part = splits.nth(0).unwrap();
-            Sensor {
-                beacon_position: parse_coordinate(beacon_part),
-                position: parse_coordinate(sensor_part),
-            }
-        })
-        .collect_vec()
+    type Parsed = Vec<Valve>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT)]
+    }
+
+    fn generate(rng: &mut Rng, size: usize, _params: &Params) -> Option<String> {
+        Some(generate_input(rng, size))
+    }
 }
 
-fn part1_impl(input: &str, y: isize) -> usize {
-    0
+fn parse_valve(line: &Line) -> Result<Valve, ParseError> {
+    let (valve, tunnels) = line.split_once("; ")?;
+    let (name, flow_rate) = valve
+        .strip_prefix("Valve ")
+        .and_then(|valve| valve.split_once(" has flow rate="))
+        .ok_or_else(|| line.error(valve, "expected 'Valve <name> has flow rate=<rate>'"))?;
+    let tunnels = tunnels
+        .strip_prefix("tunnels lead to valves ")
+        .or_else(|| tunnels.strip_prefix("tunnel leads to valve "))
+        .ok_or_else(|| line.error(tunnels, "expected 'tunnels lead to valves'"))?;
+    Ok(Valve {
+        name: String::from(name),
+        flow_rate: line.parse(flow_rate)?,
+        tunnels: tunnels.split(", ").map(String::from).collect(),
+    })
 }
 
-fn part2_impl(input: &str, max_x: isize, max_y: isize) -> isize {
-    0
+fn parse_lines(input: &str) -> Result<Vec<Valve>, ParseError> {
+    lines(input).map(|line| parse_valve(&line)).collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use std::vec;
+// `size` valves, at least two and at most one for every two-letter name, starting with `AA`.
+// The tunnels connect them all, with a few loops, and about a third of the valves have a
+// flow rate.
+fn generate_input(rng: &mut Rng, size: usize) -> String {
+    let mut names = ('A'..='Z')
+        .flat_map(|first| ('A'..='Z').map(move |second| format!("{}{}", first, second)))
+        .filter(|name| name != "AA")
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut names);
+    names.insert(0, String::from("AA"));
+    names.truncate(size.clamp(2, 26 * 26));
 
-    use super::*;
+    let count = names.len();
+    let mut tunnels = vec![vec![]; count];
+    let connect = |a: usize, b: usize, tunnels: &mut Vec<Vec<usize>>| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for valve in 1..count {
+        connect(valve, rng.below(valve), &mut tunnels);
+    }
+    for _ in 0..count / 3 {
+        connect(rng.below(count), rng.below(count), &mut tunnels);
+    }
 
-    static TEST_INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB\nValve BB has flow rate=13; tunnels lead to valves CC, AA\nValve CC has flow rate=2; tunnels lead to valves DD, BB\nValve DD has flow rate=20; tunnels lead to valves CC, AA, EE\nValve EE has flow rate=3; tunnels lead to valves FF, DD\nValve FF has flow rate=0; tunnels lead to valves EE, GG\nValve GG has flow rate=0; tunnels lead to valves FF, HH\nValve HH has flow rate=22; tunnel leads to valve GG\nValve II has flow rate=0; tunnels lead to valves AA, JJ\nValve JJ has flow rate=21; tunnel leads to valve II";
+    let mut lines = (0..count)
+        .map(|valve| {
+            let flow_rate = if valve > 0 && rng.chance(35) {
+                rng.between(1, 25)
+            } else {
+                0
+            };
+            let leads_to = tunnels[valve]
+                .iter()
+                .map(|&other| names[other].as_str())
+                .collect::<Vec<_>>();
+            let tunnels = if leads_to.len() == 1 {
+                format!("tunnel leads to valve {}", leads_to[0])
+            } else {
+                format!("tunnels lead to valves {}", leads_to.join(", "))
+            };
+            format!(
+                "Valve {} has flow rate={}; {}",
+                names[valve], flow_rate, tunnels
+            )
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut lines);
+    lines.join("\n")
+}
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1_impl(TEST_INPUT, 10), 26);
-    }
+static EXAMPLE_INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB\nValve BB has flow rate=13; tunnels lead to valves CC, AA\nValve CC has flow rate=2; tunnels lead to valves DD, BB\nValve DD has flow rate=20; tunnels lead to valves CC, AA, EE\nValve EE has flow rate=3; tunnels lead to valves FF, DD\nValve FF has flow rate=0; tunnels lead to valves EE, GG\nValve GG has flow rate=0; tunnels lead to valves FF, HH\nValve HH has flow rate=22; tunnel leads to valve GG\nValve II has flow rate=0; tunnels lead to valves AA, JJ\nValve JJ has flow rate=21; tunnel leads to valve II";
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(part2_impl(TEST_INPUT, 20, 20), 56000011);
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_parse_lines() {
+        let valves = parse_lines(EXAMPLE_INPUT).unwrap();
+        assert_eq!(valves.len(), 10);
         assert_eq!(
-            parse_lines(TEST_INPUT),
-            vec![
-                Sensor {
-                    beacon_position: (-2, 15),
-                    position: (2, 18)
-                },
-                Sensor {
-                    beacon_position: (10, 16),
-                    position: (9, 16)
-                },
-                Sensor {
-                    beacon_position: (15, 3),
-                    position: (13, 2)
-                },
-                Sensor {
-                    beacon_position: (10, 16),
-                    position: (12, 14)
-                },
-                Sensor {
-                    beacon_position: (10, 16),
-                    position: (10, 20)
-                },
-                Sensor {
-                    beacon_position: (10, 16),
-                    position: (14, 17)
-                },
-                Sensor {
-                    beacon_position: (2, 10),
-                    position: (8, 7)
-                },
-                Sensor {
-                    beacon_position: (2, 10),
-                    position: (2, 0)
-                },
-                Sensor {
-                    beacon_position: (2, 10),
-                    position: (0, 11)
-                },
-                Sensor {
-                    beacon_position: (25, 17),
-                    position: (20, 14)
-                },
-                Sensor {
-                    beacon_position: (21, 22),
-                    position: (17, 20)
-                },
-                Sensor {
-                    beacon_position: (15, 3),
-                    position: (16, 7)
-                },
-                Sensor {
-                    beacon_position: (15, 3),
-                    position: (14, 3)
-                },
-                Sensor {
-                    beacon_position: (15, 3),
-                    position: (20, 1)
-                }
-            ]
-        )
+            valves[0],
+            Valve {
+                name: String::from("AA"),
+                flow_rate: 0,
+                tunnels: vec![String::from("DD"), String::from("II"), String::from("BB")]
+            }
+        );
+        assert_eq!(
+            valves[7],
+            Valve {
+                name: String::from("HH"),
+                flow_rate: 22,
+                tunnels: vec![String::from("GG")]
+            }
+        );
     }
 }
@@ -0,0 +1,111 @@
+use std::cmp::Ordering;
+
+use crate::{day13, days};
+
+// Parses the rest of `data` with the day its first byte picks, so a single fuzz target covers
+// every parser. Errors are expected, a panic is a finding.
+pub fn parse_any(data: &[u8]) {
+    let days = days();
+    if let Some((&selector, input)) = data.split_first() {
+        let day = &days[selector as usize % days.len()];
+        (day.parse)(&String::from_utf8_lossy(input)).ok();
+    }
+}
+
+// Parses the first two lines of `data` as packets and checks that comparing them is
+// antisymmetric and that each equals itself
+pub fn packet_order(data: &[u8]) {
+    let data = String::from_utf8_lossy(data);
+    let mut lines = data.lines();
+    let (left, right) = match (lines.next(), lines.next()) {
+        (Some(left), Some(right)) => (left, right),
+        _ => return,
+    };
+    if let (Ok(left), Ok(right)) = (left.parse(), right.parse()) {
+        let order = day13::compare_packets(&left, &right);
+        assert_eq!(day13::compare_packets(&right, &left), order.reverse());
+        assert_eq!(day13::compare_packets(&left, &left), Ordering::Equal);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    // Text that tends to reach deeper into the parsers than random characters
+    const TOKENS: &[&str] = &[
+        "0",
+        "1",
+        "-1",
+        "99999999999999999999",
+        " ",
+        "\n",
+        "\n\n",
+        ",",
+        "-",
+        "=",
+        ":",
+        "[",
+        "]",
+        "x=",
+        "\u{e9}",
+    ];
+
+    // Deletes, inserts, replaces or truncates at `position`, wrapped to the input's length
+    fn mutate(input: &str, edits: &[(usize, u8, usize)]) -> String {
+        let mut chars = input.chars().collect::<Vec<_>>();
+        for &(position, kind, token) in edits {
+            let token = TOKENS[token % TOKENS.len()].chars();
+            match kind % 4 {
+                0 if !chars.is_empty() => {
+                    chars.remove(position % chars.len());
+                }
+                1 => {
+                    let position = position % (chars.len() + 1);
+                    chars.splice(position..position, token);
+                }
+                2 if !chars.is_empty() => {
+                    let position = position % chars.len();
+                    chars.splice(position..=position, token);
+                }
+                3 => chars.truncate(position % (chars.len() + 1)),
+                _ => {}
+            }
+        }
+        chars.into_iter().collect()
+    }
+
+    #[test]
+    fn test_mutate() {
+        assert_eq!(mutate("1-2", &[(1, 0, 0)]), "12");
+        assert_eq!(mutate("1-2", &[(3, 1, 5)]), "1-2\n");
+        assert_eq!(mutate("1-2", &[(4, 2, 7)]), "1,2");
+        assert_eq!(mutate("1-2", &[(2, 3, 0)]), "1-");
+        assert_eq!(mutate("", &[(0, 0, 0), (0, 2, 0)]), "");
+    }
+
+    proptest! {
+        #[test]
+        fn parsers_never_panic(data in prop::collection::vec(any::<u8>(), 0..400)) {
+            parse_any(&data);
+        }
+
+        #[test]
+        fn parsers_never_panic_on_mutated_examples(
+            edits in prop::collection::vec((any::<usize>(), any::<u8>(), any::<usize>()), 1..8)
+        ) {
+            for day in days() {
+                for example in (day.examples)() {
+                    (day.parse)(&mutate(example.input, &edits)).ok();
+                }
+            }
+        }
+
+        #[test]
+        fn packet_order_never_panics(left in "[\\[\\],0-9]{0,30}", right in "[\\[\\],0-9]{0,30}") {
+            packet_order(format!("{}\n{}", left, right).as_bytes());
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{Day, Params};
+
+// SplitMix64, small and fast and plenty random for making up puzzle inputs. The same seed
+// always gives the same numbers, so a generated input can be reproduced from its seed.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in `low..=high`
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "empty range {}..={}", low, high);
+        let span = (high as i128 - low as i128 + 1) as u128;
+        (low as i128 + ((self.next_u64() as u128 * span) >> 64) as i128) as i64
+    }
+
+    // Uniform in `0..bound`
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "empty range 0..0");
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+
+    pub fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+// A random input for `day` of roughly `size` that fits `params`, `None` when the day has no
+// generator
+pub fn generate(day: &Day, size: usize, seed: u64, params: &Params) -> Option<String> {
+    (day.generate)(&mut Rng::new(seed), size, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{days, runner::example_params, worker::run_day_isolated};
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(42);
+        let numbers = (0..1000).map(|_| rng.between(-3, 3)).collect::<Vec<_>>();
+        assert!(numbers.iter().all(|n| (-3..=3).contains(n)));
+        assert!((-3..=3).all(|n| numbers.contains(&n)));
+        assert_eq!(Rng::new(42).between(-3, 3), numbers[0]);
+        assert_eq!(
+            Rng::new(7).between(i64::MIN, i64::MAX),
+            -2032282435962401321
+        );
+
+        let mut items = (0..10).collect::<Vec<_>>();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..10).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+
+    // Generated inputs parse and every part gets through them, at a few sizes and seeds. Days
+    // run with their first example's parameters, which keeps the real-sized ones quick.
+    #[test]
+    fn test_generate() {
+        for day in days() {
+            let params = match (day.examples)().first() {
+                Some(example) => example_params(&day, example).unwrap(),
+                None => Params::new(day.params),
+            };
+            for (size, seed) in [(1, 1), (10, 2), (50, 3)] {
+                let input = match generate(&day, size, seed, &params) {
+                    Some(input) => input,
+                    None => continue,
+                };
+                assert_eq!(generate(&day, size, seed, &params).as_ref(), Some(&input));
+                // a tiny input has too few choices to tell seeds apart
+                if size >= 10 {
+                    assert_ne!(
+                        generate(&day, size, seed + 1, &params).as_ref(),
+                        Some(&input)
+                    );
+                }
+
+                let timeout = Some(Duration::from_secs(30));
+                let result = run_day_isolated(day.number, &day, &input, &params, timeout)
+                    .unwrap_or_else(|err| panic!("{}\n{}", err.diagnostic(), input));
+                for part in result.parts {
+                    assert_eq!(
+                        part.failure, None,
+                        "day {} part {} on size {} seed {}",
+                        day.number, part.part, size, seed
+                    );
+                }
+            }
+        }
+    }
+}
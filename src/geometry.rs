@@ -0,0 +1,309 @@
+use std::{
+    cmp,
+    convert::TryFrom,
+    fmt,
+    num::TryFromIntError,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::grid::Position;
+
+// A point on the plane, with `y` growing downwards like the rows of a puzzle's map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> isize {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    // Steps a king needs on a chess board, diagonal steps counting as one
+    pub fn chebyshev(self, other: Point) -> isize {
+        cmp::max((self.x - other.x).abs(), (self.y - other.y).abs())
+    }
+
+    // -1, 0 or 1 in each axis
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    // One step, diagonal if needed, closer to `target`
+    pub fn step_towards(self, target: Point) -> Point {
+        self + (target - self).signum()
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Point {
+        Point::new(x, y)
+    }
+}
+
+impl From<Position> for Point {
+    fn from((x, y): Position) -> Point {
+        Point::new(x as isize, y as isize)
+    }
+}
+
+// Fails for points left of or above the origin, which have no position on a grid
+impl TryFrom<Point> for Position {
+    type Error = TryFromIntError;
+
+    fn try_from(point: Point) -> Result<Position, TryFromIntError> {
+        Ok((usize::try_from(point.x)?, usize::try_from(point.y)?))
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: isize) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, direction: Direction) -> Point {
+        self + direction.offset()
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = *self + direction;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    // Clockwise from up
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    // A step this way, up being towards smaller `y`
+    pub fn offset(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+// The straight line from `start` to `end`, both included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Segment {
+    pub fn new(start: Point, end: Point) -> Segment {
+        Segment { start, end }
+    }
+
+    // Every point from `start` to `end`, which takes a horizontal, vertical or diagonal segment
+    pub fn points(self) -> impl Iterator<Item = Point> {
+        let step = (self.end - self.start).signum();
+        (0..=self.start.chebyshev(self.end)).map(move |i| self.start + step * i)
+    }
+
+    fn contains_in_box(self, point: Point) -> bool {
+        (cmp::min(self.start.x, self.end.x)..=cmp::max(self.start.x, self.end.x)).contains(&point.x)
+            && (cmp::min(self.start.y, self.end.y)..=cmp::max(self.start.y, self.end.y))
+                .contains(&point.y)
+    }
+
+    // The point where the segments cross, `None` when they do not, are parallel or cross
+    // between points
+    pub fn intersection(self, other: Segment) -> Option<Point> {
+        // each line as a * x + b * y = c, in i128 so that coordinates in the millions can
+        // be multiplied
+        let line = |segment: Segment| {
+            let a = (segment.end.y - segment.start.y) as i128;
+            let b = (segment.start.x - segment.end.x) as i128;
+            (
+                a,
+                b,
+                a * segment.start.x as i128 + b * segment.start.y as i128,
+            )
+        };
+        let (a1, b1, c1) = line(self);
+        let (a2, b2, c2) = line(other);
+
+        let delta = a1 * b2 - a2 * b1;
+        if delta == 0 {
+            return None;
+        }
+        let (x, y) = (b2 * c1 - b1 * c2, a1 * c2 - a2 * c1);
+        if x % delta != 0 || y % delta != 0 {
+            return None;
+        }
+        let point = Point::new((x / delta) as isize, (y / delta) as isize);
+        (self.contains_in_box(point) && other.contains_in_box(point)).then_some(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distances() {
+        assert_eq!(Point::new(0, 0).manhattan(Point::new(5, 10)), 15);
+        assert_eq!(Point::new(23, 6).manhattan(Point::new(17, 9)), 9);
+        assert_eq!(Point::new(23, 6).chebyshev(Point::new(17, 9)), 6);
+        assert_eq!(Point::new(-1, -1).chebyshev(Point::ORIGIN), 1);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let point = Point::new(3, -2);
+        assert_eq!(point + Point::new(1, 1), Point::new(4, -1));
+        assert_eq!(point - Point::new(1, 1), Point::new(2, -3));
+        assert_eq!(point * 3, Point::new(9, -6));
+        assert_eq!(-point, Point::new(-3, 2));
+        assert_eq!(point.signum(), Point::new(1, -1));
+        assert_eq!(Point::ORIGIN.step_towards(point), Point::new(1, -1));
+        assert_eq!(Point::new(3, 5).step_towards(point), Point::new(3, 4));
+        assert_eq!(Point::from((3_isize, -2_isize)), point);
+        assert_eq!(point.to_string(), "(3, -2)");
+    }
+
+    #[test]
+    fn test_position() {
+        assert_eq!(Point::from((3_usize, 5_usize)), Point::new(3, 5));
+        assert_eq!(Position::try_from(Point::new(3, 5)), Ok((3, 5)));
+        assert!(Position::try_from(Point::new(-1, 5)).is_err());
+        assert!(Position::try_from(Point::new(3, -5)).is_err());
+    }
+
+    #[test]
+    fn test_direction() {
+        assert_eq!(Point::ORIGIN + Direction::Up, Point::new(0, -1));
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.offset() + direction.reverse().offset(),
+                Point::ORIGIN
+            );
+            assert_eq!(direction.turn_right().turn_left(), direction);
+        }
+    }
+
+    #[test]
+    fn test_points() {
+        let points = |x1, y1, x2, y2| {
+            Segment::new(Point::new(x1, y1), Point::new(x2, y2))
+                .points()
+                .map(|point| (point.x, point.y))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(points(498, 4, 498, 6), vec![(498, 4), (498, 5), (498, 6)]);
+        assert_eq!(points(503, 4, 502, 4), vec![(503, 4), (502, 4)]);
+        assert_eq!(points(1, 1, 3, -1), vec![(1, 1), (2, 0), (3, -1)]);
+        assert_eq!(points(7, 7, 7, 7), vec![(7, 7)]);
+    }
+
+    #[test]
+    fn test_intersection() {
+        let segment = |x1, y1, x2, y2| Segment::new(Point::new(x1, y1), Point::new(x2, y2));
+        assert_eq!(
+            segment(4, 0, 6, 10).intersection(segment(0, 3, 10, 7)),
+            Some(Point::new(5, 5))
+        );
+        // parallel
+        assert_eq!(segment(0, 0, 1, 1).intersection(segment(1, 2, 4, 5)), None);
+        // the lines cross past the end of the first segment
+        assert_eq!(
+            segment(4, 0, 6, 10).intersection(segment(0, 15, 10, 15)),
+            None
+        );
+        // the lines cross at (0.5, 0.5)
+        assert_eq!(segment(0, 0, 1, 1).intersection(segment(0, 1, 1, 0)), None);
+        assert_eq!(
+            segment(0, 0, 2, 2).intersection(segment(0, 2, 2, 0)),
+            Some(Point::new(1, 1))
+        );
+    }
+}
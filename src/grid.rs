@@ -0,0 +1,324 @@
+use std::{
+    fmt, iter,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    geometry::{Direction, Point},
+    parse_error::lines,
+    ParseError,
+};
+
+// `(x, y)` with `x` counting columns from the left and `y` rows from the top
+pub type Position = (usize, usize);
+
+// A rectangle of cells stored row after row in a single `Vec`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cell: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    // Rows of equal length, `None` when they differ
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    // A line of input per row and a character per cell, `cell` turning characters into cells
+    // and `expected` describing the ones it accepts for errors about those it does not
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for line in lines(input) {
+            for (index, c) in line.text.char_indices() {
+                cells.push(cell(c).ok_or_else(|| {
+                    line.error(&line.text[index..index + c.len_utf8()], expected)
+                })?);
+            }
+            let line_width = line.text.chars().count();
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(line.error(
+                        line.text,
+                        &format!("expected {} cells like the first line", width),
+                    ))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        if self.contains(position) {
+            Some(&self.cells[position.1 * self.width + position.0])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.1 * self.width + position.0])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator + '_ {
+        // `chunks` would yield nothing for an empty width however many rows there are
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        assert!(x < self.width, "column {} of {}", x, self.width);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    // Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // The position `offset` away from `position`, `None` when that is outside the grid
+    pub fn step(&self, (x, y): Position, offset: Point) -> Option<Position> {
+        let next = (
+            x.checked_add_signed(offset.x)?,
+            y.checked_add_signed(offset.y)?,
+        );
+        self.contains(next).then_some(next)
+    }
+
+    // The cells from next to `position` on to the edge of the grid, going `direction`
+    pub fn walk(&self, position: Position, direction: Direction) -> impl Iterator<Item = &T> {
+        let offset = direction.offset();
+        iter::successors(self.step(position, offset), move |&next| {
+            self.step(next, offset)
+        })
+        .map(move |next| &self[next])
+    }
+
+    // Left, right, up and down of `position`, those inside the grid
+    pub fn neighbours4(&self, (x, y): Position) -> impl Iterator<Item = Position> + '_ {
+        // by value, an array's `into_iter` method yields references before edition 2021
+        IntoIterator::into_iter([
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ])
+        .filter(move |&position| self.contains(position))
+    }
+
+    // The neighbours with the diagonals, going around from the top left
+    pub fn neighbours8(&self, (x, y): Position) -> impl Iterator<Item = Position> + '_ {
+        let (left, up) = (x.wrapping_sub(1), y.wrapping_sub(1));
+        IntoIterator::into_iter([
+            (left, up),
+            (x, up),
+            (x + 1, up),
+            (x + 1, y),
+            (x + 1, y + 1),
+            (x, y + 1),
+            (left, y + 1),
+            (left, y),
+        ])
+        .filter(move |&position| self.contains(position))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "{:?} is outside a {} by {} grid",
+                position, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside a {} by {} grid", position, width, height))
+    }
+}
+
+// A line per row and the cells next to each other, which reads back with `parse` for cells
+// shown as a single character
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            row.iter().try_for_each(|cell| write!(f, "{}", cell))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, |c| c.to_digit(10), "expected a digit")
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(0, 1)], 4);
+        assert_eq!(grid.get((2, 0)), Some(&3));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(digits("").unwrap(), Grid::from_rows(vec![]).unwrap());
+
+        let error = digits("123\n4x6").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = digits("123\n45").unwrap_err();
+        assert_eq!(error.message, "expected 3 cells like the first line");
+    }
+
+    #[test]
+    fn test_from_rows() {
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2], vec![3, 4]]),
+            Some(digits("12\n34").unwrap())
+        );
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 6]]
+        );
+        assert_eq!(grid.column(1).collect::<Vec<_>>(), vec![&2, &5]);
+        assert_eq!(grid.column(2).rev().collect::<Vec<_>>(), vec![&6, &3]);
+        assert_eq!(
+            grid.positions().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+        assert_eq!(grid.position(|&cell| cell > 4), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits("123\n456\n789").unwrap();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 1), (2, 1), (1, 0), (1, 2)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbours8((2, 2)).collect::<Vec<_>>(),
+            vec![(1, 1), (2, 1), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_step_and_walk() {
+        let grid = digits("123\n456\n789").unwrap();
+        assert_eq!(grid.step((0, 0), Point::new(1, 2)), Some((1, 2)));
+        assert_eq!(grid.step((0, 0), Point::new(-1, 0)), None);
+        assert_eq!(grid.step((2, 2), Direction::Down.offset()), None);
+        assert_eq!(
+            grid.walk((0, 1), Direction::Right).collect::<Vec<_>>(),
+            vec![&5, &6]
+        );
+        assert_eq!(
+            grid.walk((1, 2), Direction::Up).collect::<Vec<_>>(),
+            vec![&5, &2]
+        );
+        assert_eq!(grid.walk((0, 1), Direction::Left).count(), 0);
+    }
+
+    #[test]
+    fn test_display() {
+        let mut grid = digits("123\n456").unwrap();
+        grid[(1, 0)] = 0;
+        assert_eq!(grid.to_string(), "103\n456");
+        assert_eq!(grid.map(|cell| cell % 2).to_string(), "101\n010");
+    }
+}
@@ -0,0 +1,397 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{runner::DayResult, Params};
+
+pub const DEFAULT_PATH: &str = "target/history.csv";
+const HEADER: &str = "timestamp,day,part,input_hash,params,nanos";
+// fewer earlier runs than this are too noisy to call a regression
+const MIN_RUNS: usize = 3;
+
+// One timed part of one run, a line of the history file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    // seconds since the Unix epoch
+    pub timestamp: u64,
+    pub day: u32,
+    pub part: u32,
+    pub input_hash: String,
+    // puzzle parameters of the run as `name=value` pairs, timings only compare when these match
+    pub params: String,
+    pub duration: Duration,
+}
+
+impl Entry {
+    fn to_line(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.timestamp,
+            self.day,
+            self.part,
+            self.input_hash,
+            self.params,
+            self.duration.as_nanos()
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Entry> {
+        match line.split(',').collect::<Vec<_>>().as_slice() {
+            [timestamp, day, part, input_hash, params, nanos] => Some(Entry {
+                timestamp: timestamp.parse().ok()?,
+                day: day.parse().ok()?,
+                part: part.parse().ok()?,
+                input_hash: String::from(*input_hash),
+                params: String::from(*params),
+                duration: Duration::from_nanos(nanos.parse().ok()?),
+            }),
+            _ => None,
+        }
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+// Entries for the parts of a run that produced an answer
+pub fn entries(
+    result: &DayResult,
+    input_hash: &str,
+    params: &Params,
+    timestamp: u64,
+) -> Vec<Entry> {
+    result
+        .parts
+        .iter()
+        .filter(|part| part.failure.is_none())
+        .map(|part| Entry {
+            timestamp,
+            day: result.day,
+            part: part.part,
+            input_hash: String::from(input_hash),
+            params: params.to_string(),
+            duration: part.duration,
+        })
+        .collect()
+}
+
+// Every entry in the file, skipping lines it cannot read; a missing file is an empty history
+pub fn load(path: &Path) -> io::Result<Vec<Entry>> {
+    match fs::read_to_string(path) {
+        Ok(history) => Ok(history.lines().filter_map(Entry::from_line).collect()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(err) => Err(err),
+    }
+}
+
+pub fn append(path: &Path, entries: &[Entry]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if is_new {
+        writeln!(file, "{}", HEADER)?;
+    }
+    entries
+        .iter()
+        .try_for_each(|entry| writeln!(file, "{}", entry.to_line()))
+}
+
+pub fn median(durations: &[Duration]) -> Option<Duration> {
+    let mut sorted = durations.to_vec();
+    sorted.sort();
+    match sorted.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(sorted[n / 2]),
+        n => Some((sorted[n / 2 - 1] + sorted[n / 2]) / 2),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    Regressed {
+        day: u32,
+        part: u32,
+        duration: Duration,
+        median: Duration,
+    },
+    OverBudget {
+        day: u32,
+        duration: Duration,
+        budget: Duration,
+    },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warning::Regressed {
+                day,
+                part,
+                duration,
+                median,
+            } => write!(
+                f,
+                "Day {} part {} REGRESSED: {:?} against a median of {:?} (+{:.0}%)",
+                day,
+                part,
+                duration,
+                median,
+                (duration.as_secs_f64() / median.as_secs_f64() - 1.0) * 100.0
+            ),
+            Warning::OverBudget {
+                day,
+                duration,
+                budget,
+            } => write!(
+                f,
+                "Day {} OVER BUDGET: {:?} against {:?}",
+                day, duration, budget
+            ),
+        }
+    }
+}
+
+// Compares each new entry with the median of earlier runs of the same part on the same input
+// and parameters, `threshold` being the allowed slowdown as a fraction (0.2 for 20%)
+pub fn regressions(history: &[Entry], current: &[Entry], threshold: f64) -> Vec<Warning> {
+    current
+        .iter()
+        .filter_map(|entry| {
+            let earlier = history
+                .iter()
+                .filter(|earlier| {
+                    earlier.day == entry.day
+                        && earlier.part == entry.part
+                        && earlier.input_hash == entry.input_hash
+                        && earlier.params == entry.params
+                })
+                .map(|earlier| earlier.duration)
+                .collect::<Vec<_>>();
+            if earlier.len() < MIN_RUNS {
+                return None;
+            }
+            let median = median(&earlier)?;
+            if entry.duration.as_secs_f64() > median.as_secs_f64() * (1.0 + threshold) {
+                Some(Warning::Regressed {
+                    day: entry.day,
+                    part: entry.part,
+                    duration: entry.duration,
+                    median,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// Time allowed for a day, from `--budget` values that are either `<seconds>` for every day or
+// `<day>=<seconds>` for a single one, which wins over the former
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Budgets {
+    every_day: Option<Duration>,
+    days: BTreeMap<u32, Duration>,
+}
+
+impl Budgets {
+    pub fn parse(values: &[String]) -> Result<Budgets, String> {
+        let mut budgets = Budgets::default();
+        for value in values {
+            let (day, secs) = match value.split_once('=') {
+                Some((day, secs)) => (Some(day.trim()), secs),
+                None => (None, value.as_str()),
+            };
+            let budget = match secs.trim().parse::<f64>() {
+                Ok(secs) if secs > 0.0 && secs.is_finite() => Duration::from_secs_f64(secs),
+                _ => {
+                    return Err(format!(
+                        "Invalid value for --budget: {}, expected <seconds> or <day>=<seconds>",
+                        value
+                    ))
+                }
+            };
+            match day.map(str::parse::<u32>) {
+                None => budgets.every_day = Some(budget),
+                Some(Ok(day)) => {
+                    budgets.days.insert(day, budget);
+                }
+                Some(Err(_)) => return Err(format!("Invalid day in --budget: {}", value)),
+            }
+        }
+        Ok(budgets)
+    }
+
+    pub fn get(&self, day: u32) -> Option<Duration> {
+        self.days.get(&day).copied().or(self.every_day)
+    }
+}
+
+// Parsing and all parts of a day together against `budget`
+pub fn over_budget(result: &DayResult, budget: Duration) -> Option<Warning> {
+    let duration = result.total_duration();
+    if duration > budget {
+        Some(Warning::OverBudget {
+            day: result.day,
+            duration,
+            budget,
+        })
+    } else {
+        None
+    }
+}
+
+// `timestamp` as "YYYY-MM-DD HH:MM:SS" in UTC
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let seconds = timestamp % 86400;
+    // civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    fn entry(part: u32, input_hash: &str, micros: u64) -> Entry {
+        Entry {
+            timestamp: 1670000000,
+            day: 11,
+            part,
+            input_hash: String::from(input_hash),
+            params: String::from("part1_rounds=20 part2_rounds=10000"),
+            duration: Duration::from_micros(micros),
+        }
+    }
+
+    #[test]
+    fn test_entry_line() {
+        let entry = entry(2, "00ff", 1500);
+        assert_eq!(
+            entry.to_line(),
+            "1670000000,11,2,00ff,part1_rounds=20 part2_rounds=10000,1500000"
+        );
+        assert_eq!(Entry::from_line(&entry.to_line()), Some(entry));
+        assert_eq!(Entry::from_line(HEADER), None);
+    }
+
+    #[test]
+    fn test_load_and_append() {
+        let path = env::temp_dir()
+            .join(format!("history-test-{}", process::id()))
+            .join("history.csv");
+        assert_eq!(load(&path).unwrap(), vec![]);
+        append(&path, &[entry(1, "00ff", 10)]).unwrap();
+        append(&path, &[entry(2, "00ff", 20)]).unwrap();
+        assert_eq!(
+            load(&path).unwrap(),
+            vec![entry(1, "00ff", 10), entry(2, "00ff", 20)]
+        );
+        assert!(fs::read_to_string(&path).unwrap().starts_with(HEADER));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_median() {
+        let micros = |values: &[u64]| {
+            values
+                .iter()
+                .map(|&value| Duration::from_micros(value))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(median(&[]), None);
+        assert_eq!(median(&micros(&[3, 1, 2])), Some(Duration::from_micros(2)));
+        assert_eq!(
+            median(&micros(&[4, 1, 2, 3])),
+            Some(Duration::from_micros(2) + Duration::from_nanos(500))
+        );
+    }
+
+    #[test]
+    fn test_regressions() {
+        let history = vec![
+            entry(1, "00ff", 100),
+            entry(1, "00ff", 110),
+            entry(1, "00ff", 90),
+            entry(1, "abcd", 10),
+            entry(2, "00ff", 100),
+        ];
+        assert_eq!(
+            regressions(
+                &history,
+                &[entry(1, "00ff", 130), entry(2, "00ff", 500)],
+                0.2
+            ),
+            vec![Warning::Regressed {
+                day: 11,
+                part: 1,
+                duration: Duration::from_micros(130),
+                median: Duration::from_micros(100),
+            }]
+        );
+        assert!(regressions(&history, &[entry(1, "00ff", 115)], 0.2).is_empty());
+        // only one earlier run on that input
+        assert!(regressions(&history, &[entry(1, "abcd", 500)], 0.2).is_empty());
+        let other_params = Entry {
+            params: String::from("part1_rounds=40 part2_rounds=10000"),
+            ..entry(1, "00ff", 500)
+        };
+        assert!(regressions(&history, &[other_params], 0.2).is_empty());
+    }
+
+    #[test]
+    fn test_budgets() {
+        let values = |values: &[&str]| values.iter().map(|&v| String::from(v)).collect::<Vec<_>>();
+        let budgets = Budgets::parse(&values(&["0.5", "12=2", "15=0.25"])).unwrap();
+        assert_eq!(budgets.get(1), Some(Duration::from_millis(500)));
+        assert_eq!(budgets.get(12), Some(Duration::from_secs(2)));
+        assert_eq!(budgets.get(15), Some(Duration::from_millis(250)));
+        let budgets = Budgets::parse(&values(&["12=2"])).unwrap();
+        assert_eq!(budgets.get(1), None);
+        assert_eq!(budgets.get(12), Some(Duration::from_secs(2)));
+        assert_eq!(Budgets::parse(&[]).unwrap(), Budgets::default());
+        for invalid in ["0", "-1", "twelve=1", "12=", "12=-3"] {
+            assert!(Budgets::parse(&values(&[invalid])).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(1671062400), "2022-12-15 00:00:00");
+        assert_eq!(format_timestamp(951868799), "2000-02-29 23:59:59");
+    }
+}
@@ -0,0 +1,117 @@
+use std::{
+    error::Error,
+    fmt, fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+// Where puzzle inputs are read from
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    // a directory of `dayNN.txt` files
+    Dir(PathBuf),
+    // one file, used whatever the day
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    // `-` reads from stdin, anything else is a file path
+    pub fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    pub fn path(&self, day: u32) -> Option<PathBuf> {
+        match self {
+            InputSource::Dir(dir) => Some(dir.join(format!("day{:02}.txt", day))),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    pub fn describe(&self, day: u32) -> String {
+        match self.path(day) {
+            Some(path) => path.display().to_string(),
+            None => String::from("stdin"),
+        }
+    }
+
+    pub fn read(&self, day: u32) -> Result<String, InputError> {
+        let result = match self.path(day) {
+            Some(path) => fs::read_to_string(path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map(|_| input)
+            }
+        };
+        result.map_err(|error| InputError {
+            source: self.describe(day),
+            error,
+        })
+    }
+}
+
+// 64-bit FNV-1a of the input as hex, stable across runs and platforms to tell inputs apart
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    // the path that was tried, or "stdin"
+    pub source: String,
+    pub error: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.error.kind() == io::ErrorKind::NotFound {
+            write!(f, "Input file not found: {}", self.source)
+        } else {
+            write!(f, "Error while reading {}: {}", self.source, self.error)
+        }
+    }
+}
+
+impl Error for InputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path() {
+        let dir = InputSource::Dir(PathBuf::from("inputs"));
+        assert_eq!(dir.path(7), Some(PathBuf::from("inputs/day07.txt")));
+        assert_eq!(
+            InputSource::from_arg("example.txt").path(7),
+            Some(PathBuf::from("example.txt"))
+        );
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(InputSource::Stdin.describe(7), "stdin");
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(input_hash("1\n2"), input_hash("2\n1"));
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let source = InputSource::Dir(PathBuf::from("does/not/exist"));
+        let error = source.read(3).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Input file not found: does/not/exist/day03.txt"
+        );
+    }
+}
@@ -1,44 +1,63 @@
-// Days
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+// fuzzing entry points, only built for the targets in fuzz/ and for the parser tests
+#[cfg(any(test, feature = "fuzz"))]
+pub mod fuzz;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod normalize;
+pub mod params;
+pub mod parse_error;
+pub mod profile;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod watch;
+pub mod worker;
 
-pub fn noop(_inp: String) {}
+pub use answer::Answer;
+pub use params::{Param, Params};
+pub use parse_error::ParseError;
+pub use solution::{Day, Example, Parsed, PartFn, Solution};
 
-pub type DayFn = fn(String);
+// Declares each day module and registers the solution it defines, in day order
+macro_rules! register_days {
+    ($($module:ident::$solution:ident),* $(,)?) => {
+        $(pub mod $module;)*
 
-pub fn get_day(day: u32) -> (DayFn, DayFn) {
-    return match day {
-        1 => (day01::part1, day01::part2),
-        2 => (day02::part1, day02::part2),
-        3 => (day03::part1, day03::part2),
-        4 => (day04::part1, day04::part2),
-        5 => (day05::part1, day05::part2),
-        6 => (day06::part1, day06::part2),
-        7 => (day07::part1, day07::part2),
-        8 => (day08::part1, day08::part2),
-        9 => (day09::part1, day09::part2),
-        10 => (day10::part1, day10::part2),
-        11 => (day11::part1, day11::part2),
-        12 => (day12::part1, day12::part2),
-        13 => (day13::part1, day13::part2),
-        14 => (day14::part1, day14::part2),
-        15 => (day15::part1, day15::part2),
-        _ => {
-            println!("Unknown day: {}", day);
-            return (noop, noop);
+        pub fn days() -> Vec<Day> {
+            vec![$(Day::from_solution::<$module::$solution>()),*]
         }
     };
 }
+
+register_days! {
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+    day04::Day04,
+    day05::Day05,
+    day06::Day06,
+    day07::Day07,
+    day08::Day08,
+    day09::Day09,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+}
+
+pub fn get_day(day: u32) -> Option<Day> {
+    days()
+        .into_iter()
+        .find(|registered| registered.number == day)
+}
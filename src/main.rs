@@ -1,20 +1,55 @@
+use std::collections::HashMap;
 use std::env;
-use std::fs;
-use std::io;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+use std::thread;
 use std::time::{Duration, Instant};
 
-use advent_of_code::{get_day, noop};
+use itertools::Itertools;
+use serde_json::Value;
+
+use advent_of_code::answers::{Answers, Verdict};
+use advent_of_code::bench::{bench_day, BenchConfig, Budget};
+use advent_of_code::generate::generate;
+use advent_of_code::history::{self, Budgets, Entry};
+use advent_of_code::input::{input_hash, InputError, InputSource};
+use advent_of_code::params::parse_override;
+use advent_of_code::profile::{self, AllocStats, CountingAllocator};
+use advent_of_code::report::part_records;
+use advent_of_code::runner::{
+    example_params, implemented_parts, parse_day_selection, run_ordered, DayResult, LAST_DAY,
+};
+use advent_of_code::scaffold::{module_name, new_day};
+use advent_of_code::watch::{diff_answers, Watcher};
+use advent_of_code::worker::run_day_isolated;
+use advent_of_code::{days, get_day, Day, Params, ParseError};
+
+// Only counts while profiling with `--profile`
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const INPUTS_DIR: &str = "inputs";
+const ANSWERS_FILE: &str = "answers.toml";
+// Accepted by every mode that reads puzzle inputs
+const INPUT_FLAGS: [&str; 2] = ["--input", "--inputs-dir"];
 
 fn fmt_time(ms: f64) -> String {
+    if ms < 0.001 {
+        let nano_sec = ms * 1_000_000.0;
+        return format!("{}ns", nano_sec.round());
+    }
+
     if ms <= 1.0 {
         let micro_sec = ms * 1000.0;
-        return String::from(format!("{}µs", micro_sec.round()));
+        return format!("{}µs", micro_sec.round());
     }
 
     if ms < 1000.0 {
         let whole_ms = ms.floor();
         let rem_ms = ms - whole_ms;
-        return String::from(format!("{}ms ", whole_ms) + &fmt_time(rem_ms));
+        return format!("{}ms ", whole_ms) + &fmt_time(rem_ms);
     }
 
     let sec: f64 = ms / 1000.0;
@@ -26,20 +61,1073 @@ fn fmt_time(ms: f64) -> String {
     }
 
     let min: f64 = sec / 60.0;
-    return format!("{}m ", min.floor()) + &fmt_time((sec % 60.0) * 1000.0);
+    format!("{}m ", min.floor()) + &fmt_time((sec % 60.0) * 1000.0)
 }
 
 fn fmt_dur(dur: Duration) -> String {
-    return fmt_time(dur.as_secs_f64() * 1000.0);
+    fmt_time(dur.as_secs_f64() * 1000.0)
+}
+
+fn fmt_bytes(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}B", bytes)
+    } else {
+        format!("{:.1}{}", value, units[unit])
+    }
+}
+
+// Positional arguments and `--flag [value]` options of a subcommand, flags may repeat
+struct Args {
+    positional: Vec<String>,
+    flags: HashMap<String, Vec<Option<String>>>,
+}
+
+impl Args {
+    fn parse(args: &[String], value_flags: &[&str]) -> Args {
+        let mut positional = vec![];
+        let mut flags = HashMap::<String, Vec<Option<String>>>::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if value_flags.contains(&arg.as_str()) {
+                flags
+                    .entry(arg.clone())
+                    .or_default()
+                    .push(iter.next().cloned());
+            } else if arg.starts_with("--") {
+                flags.entry(arg.clone()).or_default().push(None);
+            } else {
+                positional.push(arg.clone());
+            }
+        }
+        Args { positional, flags }
+    }
+
+    fn has(&self, flag: &str) -> bool {
+        self.flags.contains_key(flag)
+    }
+
+    // The last value given for `flag`
+    fn value<T: FromStr>(&self, flag: &str) -> Result<Option<T>, String> {
+        self.values(flag).map(|values| values.into_iter().last())
+    }
+
+    fn values<T: FromStr>(&self, flag: &str) -> Result<Vec<T>, String> {
+        self.flags
+            .get(flag)
+            .into_iter()
+            .flatten()
+            .map(|value| match value {
+                None => Err(format!("Missing value for {}", flag)),
+                Some(value) => value
+                    .parse()
+                    .map_err(|_| format!("Invalid value for {}: {}", flag, value)),
+            })
+            .collect()
+    }
+
+    fn selection(&self) -> &str {
+        self.positional.first().map_or("all", |arg| arg.as_str())
+    }
+}
+
+fn inputs_dir(args: &Args) -> Result<PathBuf, String> {
+    match args.value("--inputs-dir")? {
+        Some(dir) => Ok(dir),
+        None => Ok(env::current_dir().unwrap().join(INPUTS_DIR)),
+    }
+}
+
+// `--input <path>` (or `-` for stdin) overrides `--inputs-dir`, for a single day only
+fn input_source(args: &Args, day_nums: &[u32]) -> Result<InputSource, String> {
+    match args.value::<String>("--input")? {
+        Some(_) if day_nums.len() != 1 => Err(format!(
+            "--input needs a single day, but {} were selected",
+            day_nums.len()
+        )),
+        Some(path) => Ok(InputSource::from_arg(&path)),
+        None => Ok(InputSource::Dir(inputs_dir(args)?)),
+    }
+}
+
+fn seconds(args: &Args, flag: &str) -> Result<Option<Duration>, String> {
+    match args.value::<f64>(flag) {
+        Ok(Some(secs)) if secs > 0.0 && secs.is_finite() => Ok(Some(Duration::from_secs_f64(secs))),
+        Ok(None) => Ok(None),
+        _ => Err(format!(
+            "Invalid value for {}, expected a positive number of seconds",
+            flag
+        )),
+    }
+}
+
+// `--param name=value` overrides, each of which must suit at least one of the selected days
+fn param_overrides(args: &Args, day_nums: &[u32]) -> Result<Vec<(String, i64)>, String> {
+    let overrides = args
+        .values::<String>("--param")?
+        .iter()
+        .map(|arg| parse_override(arg))
+        .collect::<Result<Vec<_>, _>>()?;
+    let days = day_nums
+        .iter()
+        .filter_map(|&day| get_day(day))
+        .collect::<Vec<_>>();
+    for (name, _) in &overrides {
+        if !days
+            .iter()
+            .any(|day| Params::new(day.params).declares(name))
+        {
+            return Err(format!("No selected day has a parameter named {}", name));
+        }
+    }
+    for day in &days {
+        Params::new(day.params).with_overrides(&overrides)?;
+    }
+    Ok(overrides)
+}
+
+fn print_day_result(result: &DayResult) {
+    println!("Parsing took {}", fmt_dur(result.parse_duration));
+    result.parts.iter().for_each(|part| {
+        println!("Part {}: {}", part.part, part.outcome());
+        println!("Took {}", fmt_dur(part.duration));
+    });
+}
+
+// The first two columns (day and part) are right-aligned, cells may span multiple lines
+fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let header = header
+        .iter()
+        .map(|cell| cell.to_string())
+        .collect::<Vec<_>>();
+    let widths = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .chain(std::iter::once(&header))
+                .flat_map(|row| row[column].lines().map(|line| line.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+    let print_row = |row: &Vec<String>| {
+        let height = row
+            .iter()
+            .map(|cell| cell.lines().count())
+            .max()
+            .unwrap_or(0);
+        (0..height.max(1)).for_each(|index| {
+            let formatted = row
+                .iter()
+                .zip(widths.iter())
+                .enumerate()
+                .map(|(column, (cell, width))| {
+                    let line = cell.lines().nth(index).unwrap_or("");
+                    if column < 2 {
+                        format!("{:>w$}", line, w = width)
+                    } else {
+                        format!("{:<w$}", line, w = width)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" | ");
+            println!("{}", formatted.trim_end());
+        });
+    };
+
+    print_row(&header);
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-")
+    );
+    rows.iter().for_each(print_row);
+}
+
+fn print_summary(results: &[DayResult]) {
+    let rows = results
+        .iter()
+        .flat_map(|result| {
+            result.parts.iter().map(move |part| {
+                vec![
+                    result.day.to_string(),
+                    part.part.to_string(),
+                    part.outcome().trim_start().to_string(),
+                    fmt_dur(part.duration),
+                ]
+            })
+        })
+        .collect::<Vec<_>>();
+
+    println!();
+    print_table(&["Day", "Part", "Answer", "Time"], &rows);
+
+    let total = results
+        .iter()
+        .fold(Duration::ZERO, |acc, result| acc + result.total_duration());
+    let parsing = results
+        .iter()
+        .fold(Duration::ZERO, |acc, result| acc + result.parse_duration);
+    println!();
+    println!(
+        "Total runtime: {} (of which parsing {})",
+        fmt_dur(total),
+        fmt_dur(parsing)
+    );
+}
+
+fn print_profile(results: &[DayResult]) {
+    let row = |day: u32, part: String, allocs: &AllocStats| {
+        vec![
+            day.to_string(),
+            part,
+            allocs.allocations.to_string(),
+            fmt_bytes(allocs.bytes as u64),
+            fmt_bytes(allocs.peak_live as u64),
+        ]
+    };
+    let rows = results
+        .iter()
+        .flat_map(|result| {
+            std::iter::once(row(result.day, String::from("parse"), &result.parse_allocs)).chain(
+                result
+                    .parts
+                    .iter()
+                    .map(move |part| row(result.day, part.part.to_string(), &part.allocs)),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    println!();
+    print_table(
+        &["Day", "Part", "Allocations", "Allocated", "Peak live"],
+        &rows,
+    );
+    if let Some(peak_rss) = profile::peak_rss() {
+        println!();
+        println!("Peak RSS: {}", fmt_bytes(peak_rss));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    // one JSON array with a record per executed part
+    Json,
+    // one record per line, printed as soon as a day is done
+    Ndjson,
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(format: &str) -> Result<Format, ()> {
+        match format {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            _ => Err(()),
+        }
+    }
+}
+
+// Progress and errors go to stderr when stdout is reserved for JSON
+fn status(format: Format, message: &str) {
+    if format == Format::Text {
+        println!("{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+}
+
+struct Run {
+    results: Vec<DayResult>,
+    // JSON records of every executed part
+    records: Vec<Value>,
+    // timing history entries of every part with an answer
+    history: Vec<Entry>,
+    // days that failed to parse are reported and skipped
+    parse_errors: usize,
+}
+
+// What happened to a single day, worked out on any thread and reported in day order
+enum DayOutcome {
+    Skipped(String),
+    InputError(String, InputError),
+    ParseError(String, ParseError),
+    // input description and hash and the parameters with the result
+    Done(String, String, Params, DayResult),
+}
+
+fn run_one_day(
+    day_num: u32,
+    source: &InputSource,
+    overrides: &[(String, i64)],
+    timeout: Option<Duration>,
+) -> DayOutcome {
+    // Get corresponding functions
+    let to_run = match get_day(day_num) {
+        Some(to_run) => to_run,
+        None => return DayOutcome::Skipped(format!("Unknown day: {}", day_num)),
+    };
+    if implemented_parts(&to_run).is_empty() {
+        return DayOutcome::Skipped(format!("Day {} has no implemented parts", day_num));
+    }
+
+    let params = match Params::new(to_run.params).with_overrides(overrides) {
+        Ok(params) => params,
+        Err(err) => return DayOutcome::Skipped(err),
+    };
+
+    let description = source.describe(day_num);
+    let input = match source.read(day_num) {
+        Ok(input) => input,
+        Err(err) => return DayOutcome::InputError(description, err),
+    };
+    match run_day_isolated(day_num, &to_run, &input, &params, timeout) {
+        Ok(result) => DayOutcome::Done(description, input_hash(&input), params, result),
+        Err(err) => DayOutcome::ParseError(description, err),
+    }
+}
+
+fn history_path(args: &Args) -> Result<PathBuf, String> {
+    Ok(args
+        .value("--history")?
+        .unwrap_or_else(|| PathBuf::from(history::DEFAULT_PATH)))
+}
+
+struct HistoryOptions {
+    path: PathBuf,
+    // allowed slowdown as a fraction
+    threshold: f64,
+    budgets: Budgets,
+    record: bool,
+}
+
+fn history_options(args: &Args) -> Result<HistoryOptions, String> {
+    let threshold = match args.value::<f64>("--regression")? {
+        Some(percent) if !(percent >= 0.0 && percent.is_finite()) => {
+            return Err(String::from(
+                "Invalid value for --regression, expected a percentage of at least 0",
+            ))
+        }
+        percent => percent.unwrap_or(20.0) / 100.0,
+    };
+    Ok(HistoryOptions {
+        path: history_path(args)?,
+        threshold,
+        budgets: Budgets::parse(&args.values::<String>("--budget")?)?,
+        record: !args.has("--no-history"),
+    })
+}
+
+// Flags days over their `--budget`, then, when the timings are `comparable` with earlier runs,
+// parts slower than the median of those by more than `--regression` percent, and adds the run
+// to the history unless `--no-history`
+fn check_history(
+    options: &HistoryOptions,
+    run: &Run,
+    comparable: bool,
+    format: Format,
+) -> Result<(), String> {
+    let mut warnings = vec![];
+    if comparable {
+        let path = &options.path;
+        let earlier = history::load(path)
+            .map_err(|err| format!("Error while reading {}: {}", path.display(), err))?;
+        warnings = history::regressions(&earlier, &run.history, options.threshold);
+        if options.record {
+            history::append(path, &run.history)
+                .map_err(|err| format!("Error while writing {}: {}", path.display(), err))?;
+        }
+    }
+    warnings.extend(run.results.iter().filter_map(|result| {
+        let budget = options.budgets.get(result.day)?;
+        history::over_budget(result, budget)
+    }));
+    warnings
+        .iter()
+        .for_each(|warning| status(format, &warning.to_string()));
+    Ok(())
+}
+
+// With more than one job, days run concurrently so their timings include contention
+fn run_days(
+    day_nums: &[u32],
+    source: &InputSource,
+    jobs: usize,
+    overrides: &[(String, i64)],
+    timeout: Option<Duration>,
+    verbose: bool,
+    format: Format,
+) -> Run {
+    let mut run = Run {
+        results: vec![],
+        records: vec![],
+        history: vec![],
+        parse_errors: 0,
+    };
+    let timestamp = history::now();
+    run_ordered(
+        day_nums,
+        jobs,
+        |&day_num| run_one_day(day_num, source, overrides, timeout),
+        |outcome| match outcome {
+            DayOutcome::Skipped(message) => status(format, &message),
+            DayOutcome::InputError(description, err) => {
+                status(format, &format!("Reading {}", description));
+                status(format, &err.to_string());
+            }
+            DayOutcome::ParseError(description, err) => {
+                status(format, &format!("Reading {}", description));
+                status(format, &err.diagnostic());
+                run.parse_errors += 1;
+            }
+            DayOutcome::Done(description, hash, params, result) => {
+                status(format, &format!("Reading {}", description));
+                let records = part_records(&result, &description, &hash);
+                match format {
+                    Format::Text if verbose => print_day_result(&result),
+                    Format::Ndjson => records.iter().for_each(|record| println!("{}", record)),
+                    _ => (),
+                }
+                run.history
+                    .extend(history::entries(&result, &hash, &params, timestamp));
+                run.results.push(result);
+                run.records.extend(records);
+            }
+        },
+    );
+    run
+}
+
+fn verify(args: &[String]) -> i32 {
+    // recorded answers only hold for the real inputs, so no `--input` here
+    let args = Args::parse(args, &["--inputs-dir"]);
+    let record = args.has("--record");
+    let selection = args.selection();
+    let day_nums = match parse_day_selection(selection) {
+        Some(day_nums) => day_nums,
+        None => {
+            println!("Invalid day number: {}", selection);
+            return 1;
+        }
+    };
+
+    let dir = match inputs_dir(&args) {
+        Ok(dir) => dir,
+        Err(err) => {
+            println!("{}", err);
+            return 1;
+        }
+    };
+    let path = dir.join(ANSWERS_FILE);
+    let mut answers = match Answers::load(&path) {
+        Ok(answers) => answers,
+        Err(err) => {
+            println!("Error while reading {}: {}", path.display(), err);
+            return 1;
+        }
+    };
+
+    let run = run_days(
+        &day_nums,
+        &InputSource::Dir(dir),
+        1,
+        &[],
+        None,
+        false,
+        Format::Text,
+    );
+    let mut failed = run.parse_errors > 0;
+    let mut recorded = false;
+    for result in run.results {
+        for part in &result.parts {
+            if let Some(failure) = &part.failure {
+                failed = true;
+                println!("Day {:>2} part {}: {}", result.day, part.part, failure);
+                continue;
+            }
+            let verdict = answers.verify(result.day, part.part, &part.answer);
+            let answer = part.answer.to_string();
+            let answer = answer.trim_start();
+            match &verdict {
+                Verdict::Pass => println!("Day {:>2} part {}: {}", result.day, part.part, verdict),
+                Verdict::Fail(expected) => {
+                    failed = true;
+                    println!(
+                        "Day {:>2} part {}: {} (expected {}, got {})",
+                        result.day,
+                        part.part,
+                        verdict,
+                        expected.to_string().trim_start(),
+                        answer
+                    );
+                }
+                Verdict::Missing if record => {
+                    answers.insert(result.day, part.part, &part.answer);
+                    recorded = true;
+                    println!(
+                        "Day {:>2} part {}: {} (recorded {})",
+                        result.day, part.part, verdict, answer
+                    );
+                }
+                Verdict::Missing => println!(
+                    "Day {:>2} part {}: {} (got {})",
+                    result.day, part.part, verdict, answer
+                ),
+            }
+        }
+    }
+
+    if recorded {
+        if let Err(err) = answers.save(&path) {
+            println!("Error while writing {}: {}", path.display(), err);
+            return 1;
+        }
+        println!("Recorded answers in {}", path.display());
+    }
+
+    if failed {
+        1
+    } else {
+        0
+    }
+}
+
+// Runs each selected day on the examples it registers, comparing against the expected answers
+fn run_examples(day_nums: &[u32], overrides: &[(String, i64)], timeout: Option<Duration>) -> i32 {
+    let mut failed = false;
+    for &day_num in day_nums {
+        let to_run = match get_day(day_num) {
+            Some(to_run) => to_run,
+            None => {
+                println!("Unknown day: {}", day_num);
+                continue;
+            }
+        };
+        let examples = (to_run.examples)();
+        if examples.is_empty() {
+            println!("Day {} has no examples", day_num);
+            continue;
+        }
+
+        for (index, example) in examples.iter().enumerate() {
+            println!("Day {} example {}", day_num, index + 1);
+            // the example's own parameters, then the ones given on the command line
+            let params = match example_params(&to_run, example)
+                .and_then(|params| params.with_overrides(overrides))
+            {
+                Ok(params) => params,
+                Err(err) => {
+                    println!("{}", err);
+                    failed = true;
+                    continue;
+                }
+            };
+            let result = match run_day_isolated(day_num, &to_run, example.input, &params, timeout) {
+                Ok(result) => result,
+                Err(err) => {
+                    println!("{}", err.diagnostic());
+                    failed = true;
+                    continue;
+                }
+            };
+            for part in &result.parts {
+                if let Some(failure) = &part.failure {
+                    failed = true;
+                    println!("Part {}: {}", part.part, failure);
+                    continue;
+                }
+                match Verdict::check(example.expected(part.part), &part.answer) {
+                    Verdict::Fail(expected) => {
+                        failed = true;
+                        println!(
+                            "Part {}: {} (expected {}) FAIL",
+                            part.part, part.answer, expected
+                        );
+                    }
+                    verdict => println!("Part {}: {} {}", part.part, part.answer, verdict),
+                }
+            }
+        }
+    }
+
+    if failed {
+        1
+    } else {
+        0
+    }
+}
+
+fn bench_config(args: &Args) -> Result<BenchConfig, String> {
+    let mut config = BenchConfig::default();
+    if let Some(warmup) = args.value("--warmup")? {
+        config.warmup = warmup;
+    }
+    if let Some(time) = seconds(args, "--time")? {
+        config.budget = Budget::Time(time);
+    }
+    if let Some(iterations) = args.value("--iterations")? {
+        config.budget = Budget::Iterations(iterations);
+    }
+    Ok(config)
+}
+
+fn bench(args: &[String]) -> i32 {
+    let args = Args::parse(
+        args,
+        &[
+            &["--iterations", "--time", "--warmup", "--param"][..],
+            &INPUT_FLAGS,
+        ]
+        .concat(),
+    );
+    let selection = args.selection();
+    let day_nums = match parse_day_selection(selection) {
+        Some(day_nums) => day_nums,
+        None => {
+            println!("Invalid day number: {}", selection);
+            return 1;
+        }
+    };
+
+    let (config, source, overrides) = match bench_config(&args).and_then(|config| {
+        Ok((
+            config,
+            input_source(&args, &day_nums)?,
+            param_overrides(&args, &day_nums)?,
+        ))
+    }) {
+        Ok(config_source_and_overrides) => config_source_and_overrides,
+        Err(err) => {
+            println!("{}", err);
+            return 1;
+        }
+    };
+
+    let mut rows = vec![];
+    for day_num in day_nums {
+        let to_run = match get_day(day_num) {
+            Some(to_run) => to_run,
+            None => {
+                println!("Unknown day: {}", day_num);
+                continue;
+            }
+        };
+        println!("Reading {}", source.describe(day_num));
+        let input = match source.read(day_num) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("{}", err);
+                continue;
+            }
+        };
+
+        let params = match Params::new(to_run.params).with_overrides(&overrides) {
+            Ok(params) => params,
+            Err(err) => {
+                println!("{}", err);
+                continue;
+            }
+        };
+        let results = match bench_day(&to_run, &input, &params, &config) {
+            Ok(results) => results,
+            Err(err) => {
+                println!("{}", err.in_day(day_num).diagnostic());
+                continue;
+            }
+        };
+        results.into_iter().for_each(|(part, stats)| {
+            rows.push(vec![
+                day_num.to_string(),
+                part,
+                stats.samples.to_string(),
+                fmt_time(stats.min),
+                fmt_time(stats.median),
+                fmt_time(stats.mean),
+                fmt_time(stats.p95),
+                fmt_time(stats.std_dev),
+            ])
+        });
+    }
+
+    println!();
+    print_table(
+        &[
+            "Day", "Part", "Runs", "Min", "Median", "Mean", "P95", "Std dev",
+        ],
+        &rows,
+    );
+    0
+}
+
+fn list() -> i32 {
+    let rows = days()
+        .iter()
+        .map(|day| {
+            let implemented = implemented_parts(day);
+            let mut row = vec![day.number.to_string()];
+            row.extend((1..=2).map(|part| {
+                String::from(if implemented.contains(&part) {
+                    "done"
+                } else {
+                    "noop"
+                })
+            }));
+            row.push(String::from(day.title));
+            row.push(Params::new(day.params).to_string());
+            row
+        })
+        .collect::<Vec<_>>();
+    print_table(&["Day", "Part 1", "Part 2", "Title", "Params"], &rows);
+    0
+}
+
+// Labelled answers of one run of a watched day, on its input and with `--example` its examples
+fn watch_run(
+    day: &Day,
+    source: &InputSource,
+    overrides: &[(String, i64)],
+    examples: bool,
+    timeout: Option<Duration>,
+) -> Vec<(String, String)> {
+    let mut answers = vec![];
+    let params = Params::new(day.params)
+        .with_overrides(overrides)
+        .expect("overrides are checked up front");
+    match source.read(day.number) {
+        Ok(input) => match run_day_isolated(day.number, day, &input, &params, timeout) {
+            Ok(result) => {
+                println!("Ran in {}", fmt_dur(result.total_duration()));
+                answers.extend(
+                    result
+                        .parts
+                        .iter()
+                        .map(|part| (format!("Part {}", part.part), part.outcome())),
+                );
+            }
+            Err(err) => println!("{}", err.diagnostic()),
+        },
+        Err(err) => println!("{}", err),
+    }
+
+    if !examples {
+        return answers;
+    }
+    for (index, example) in (day.examples)().iter().enumerate() {
+        let params = example_params(day, example)
+            .and_then(|params| params.with_overrides(overrides))
+            .expect("overrides are checked up front");
+        match run_day_isolated(day.number, day, example.input, &params, timeout) {
+            Ok(result) => answers.extend(result.parts.iter().map(|part| {
+                let outcome = match &part.failure {
+                    Some(failure) => failure.to_string(),
+                    None => format!(
+                        "{} {}",
+                        part.answer,
+                        Verdict::check(example.expected(part.part), &part.answer)
+                    ),
+                };
+                (format!("Example {} part {}", index + 1, part.part), outcome)
+            })),
+            Err(err) => println!("{}", err.diagnostic()),
+        }
+    }
+    answers
+}
+
+fn watch(args: &[String]) -> i32 {
+    let args = Args::parse(
+        args,
+        &[&INPUT_FLAGS[..], &["--interval", "--param", "--timeout"]].concat(),
+    );
+    let day = match args.positional.first().and_then(|day| day.parse().ok()) {
+        Some(day_num) => match get_day(day_num) {
+            Some(day) => day,
+            None => {
+                println!("Unknown day: {}", day_num);
+                return 1;
+            }
+        },
+        None => {
+            println!("Usage: watch <day> [--example] [--interval <secs>]");
+            return 1;
+        }
+    };
+
+    let config = input_source(&args, &[day.number]).and_then(|source| {
+        Ok((
+            source,
+            param_overrides(&args, &[day.number])?,
+            seconds(&args, "--interval")?.unwrap_or(Duration::from_millis(500)),
+            seconds(&args, "--timeout")?,
+        ))
+    });
+    let (source, overrides, interval, timeout) = match config {
+        Ok(config) => config,
+        Err(err) => {
+            println!("{}", err);
+            return 1;
+        }
+    };
+    let input_path = match source.path(day.number) {
+        Some(path) => path,
+        None => {
+            println!("Cannot watch stdin, pass a file with --input");
+            return 1;
+        }
+    };
+    let examples = args.has("--example");
+
+    // a running binary cannot pick up code changes, those are only pointed out
+    let source_path = PathBuf::from("src").join(format!("{}.rs", module_name(day.number)));
+    let mut watcher = Watcher::new(vec![input_path.clone(), source_path.clone()]);
+    println!(
+        "Watching {} and {}, press Ctrl-C to stop",
+        input_path.display(),
+        source_path.display()
+    );
+
+    let mut previous = watch_run(&day, &source, &overrides, examples, timeout);
+    previous
+        .iter()
+        .for_each(|(label, answer)| println!("{}: {}", label, answer));
+    loop {
+        thread::sleep(interval);
+        let changed = watcher.changed();
+        if changed.is_empty() {
+            continue;
+        }
+        println!();
+        changed
+            .iter()
+            .for_each(|path| println!("{} changed", path.display()));
+        if changed == [source_path.clone()] {
+            println!("Rebuild and restart watch to run the new code");
+            continue;
+        }
+
+        let current = watch_run(&day, &source, &overrides, examples, timeout);
+        diff_answers(&previous, &current)
+            .iter()
+            .for_each(|line| println!("{}", line));
+        previous = current;
+    }
+}
+
+fn show_history(args: &[String]) -> i32 {
+    let args = Args::parse(args, &["--history", "--limit"]);
+    let day = match args
+        .positional
+        .first()
+        .and_then(|day| day.parse::<u32>().ok())
+    {
+        Some(day) => day,
+        None => {
+            println!("Usage: history <day> [--limit <runs>] [--history <path>]");
+            return 1;
+        }
+    };
+    let (path, limit) = match history_path(&args)
+        .and_then(|path| Ok((path, args.value::<usize>("--limit")?.unwrap_or(20))))
+    {
+        Ok(path_and_limit) => path_and_limit,
+        Err(err) => {
+            println!("{}", err);
+            return 1;
+        }
+    };
+    let entries = match history::load(&path) {
+        Ok(entries) => entries
+            .into_iter()
+            .filter(|entry| entry.day == day)
+            .collect::<Vec<_>>(),
+        Err(err) => {
+            println!("Error while reading {}: {}", path.display(), err);
+            return 1;
+        }
+    };
+    if entries.is_empty() {
+        println!("No history for day {} in {}", day, path.display());
+        return 0;
+    }
+
+    let rows = entries[entries.len().saturating_sub(limit)..]
+        .iter()
+        .map(|entry| {
+            vec![
+                entry.day.to_string(),
+                entry.part.to_string(),
+                history::format_timestamp(entry.timestamp),
+                entry.input_hash.chars().take(8).collect(),
+                entry.params.clone(),
+                fmt_dur(entry.duration),
+            ]
+        })
+        .collect::<Vec<_>>();
+    print_table(
+        &["Day", "Part", "When (UTC)", "Input", "Params", "Time"],
+        &rows,
+    );
+
+    println!();
+    // medians only make sense between runs on the same input and parameters
+    let groups = entries
+        .iter()
+        .map(|entry| (entry.part, &entry.input_hash, &entry.params))
+        .unique()
+        .sorted();
+    for (part, input_hash, params) in groups {
+        let durations = entries
+            .iter()
+            .filter(|entry| {
+                (entry.part, &entry.input_hash, &entry.params) == (part, input_hash, params)
+            })
+            .map(|entry| entry.duration)
+            .collect::<Vec<_>>();
+        println!(
+            "Part {} on {} {}: median {} over {} runs",
+            part,
+            &input_hash[..input_hash.len().min(8)],
+            params,
+            fmt_dur(history::median(&durations).unwrap()),
+            durations.len()
+        );
+    }
+    0
+}
+
+fn new(args: &[String]) -> i32 {
+    let args = Args::parse(args, &["--title"]);
+    let day = match args.positional.first().map(|day| day.parse::<u32>()) {
+        Some(Ok(day)) if (1..=LAST_DAY).contains(&day) => day,
+        _ => {
+            println!(
+                "Usage: new <day> [--title <title>], with a day from 1 to {}",
+                LAST_DAY
+            );
+            return 1;
+        }
+    };
+    let title = match args.value::<String>("--title") {
+        Ok(title) => title.unwrap_or_default(),
+        Err(err) => {
+            println!("{}", err);
+            return 1;
+        }
+    };
+
+    match new_day(&env::current_dir().unwrap(), day, &title) {
+        Ok(paths) => {
+            paths
+                .iter()
+                .for_each(|path| println!("Wrote {}", path.display()));
+            0
+        }
+        Err(err) => {
+            println!("{}", err);
+            1
+        }
+    }
+}
+
+// Prints a random input for a day, reproducible from the seed that is reported on stderr
+fn gen(args: &[String]) -> i32 {
+    let args = Args::parse(args, &["--size", "--seed", "--param"]);
+    let day = match args.positional.first().and_then(|day| day.parse().ok()) {
+        Some(day_num) => match get_day(day_num) {
+            Some(day) => day,
+            None => {
+                println!("Unknown day: {}", day_num);
+                return 1;
+            }
+        },
+        None => {
+            println!("Usage: gen <day> [--size <n>] [--seed <seed>] [--param name=value]");
+            return 1;
+        }
+    };
+    let config = param_overrides(&args, &[day.number]).and_then(|overrides| {
+        Ok((
+            Params::new(day.params).with_overrides(&overrides)?,
+            args.value::<usize>("--size")?.unwrap_or(100),
+            args.value::<u64>("--seed")?,
+        ))
+    });
+    let (params, size, seed) = match config {
+        Ok(config) => config,
+        Err(err) => {
+            println!("{}", err);
+            return 1;
+        }
+    };
+    let seed = seed.unwrap_or_else(|| {
+        let seed = history::now();
+        eprintln!("Seed {}", seed);
+        seed
+    });
+
+    match generate(&day, size, seed, &params) {
+        Some(input) => {
+            // the output is often piped into `head`, a closed pipe is not an error
+            writeln!(io::stdout(), "{}", input).ok();
+            0
+        }
+        None => {
+            println!("No generator for day {}", day.number);
+            1
+        }
+    }
 }
 
 fn main() {
-    // Get day string
     let args: Vec<String> = env::args().collect();
+    if args.len() >= 2 {
+        match args[1].as_str() {
+            "verify" => process::exit(verify(&args[2..])),
+            "bench" => process::exit(bench(&args[2..])),
+            "list" => process::exit(list()),
+            "new" => process::exit(new(&args[2..])),
+            "watch" => process::exit(watch(&args[2..])),
+            "history" => process::exit(show_history(&args[2..])),
+            "gen" => process::exit(gen(&args[2..])),
+            _ => (),
+        }
+    }
+    let args = Args::parse(
+        &args[1..],
+        &[
+            &INPUT_FLAGS[..],
+            &[
+                "--format",
+                "--jobs",
+                "--timeout",
+                "--param",
+                "--history",
+                "--regression",
+                "--budget",
+            ],
+        ]
+        .concat(),
+    );
+
+    // Get day string
     let mut day = String::new();
 
-    if args.len() >= 2 {
-        day = args[1].clone();
+    if let Some(selection) = args.positional.first() {
+        day = selection.clone();
+    } else if args.value::<String>("--input") == Ok(Some(String::from("-"))) {
+        println!("A day is required when reading the input from stdin");
+        process::exit(1);
     } else {
         println!("Enter day: ");
         io::stdin()
@@ -47,39 +1135,95 @@ fn main() {
             .expect("Failed to read line");
     }
 
-    // Parse day as number
+    // Parse day, range of days or "all"
     day = day.trim().to_string();
-    let day_num: u32 = match day.parse() {
-        Ok(num) => num,
-        Err(_) => {
+    let day_nums = match parse_day_selection(&day) {
+        Some(day_nums) => day_nums,
+        None => {
             println!("Invalid day number: {}", day);
             return;
         }
     };
+    let show_summary = day_nums.len() > 1 || day == "all";
 
-    // Read input file
-    let cwd = env::current_dir().unwrap();
-    let filename = cwd.join("inputs").join(format!("day{:02}.txt", day_num));
-    println!("Reading {}", filename.display());
-    let input = fs::read_to_string(filename).expect("Error while reading");
+    let timeout = match seconds(&args, "--timeout") {
+        Ok(timeout) => timeout,
+        Err(err) => {
+            println!("{}", err);
+            process::exit(1);
+        }
+    };
 
-    // Get corresponding function
-    let to_run = get_day(day_num);
+    let overrides = match param_overrides(&args, &day_nums) {
+        Ok(overrides) => overrides,
+        Err(err) => {
+            println!("{}", err);
+            process::exit(1);
+        }
+    };
 
-    // Time it
-    if to_run.0 != noop {
-        println!("Running Part 1");
-        let part1_start = Instant::now();
-        to_run.0(input.clone());
-        let part1_dur = part1_start.elapsed();
-        println!("Took {}", fmt_dur(part1_dur));
+    if args.has("--example") {
+        process::exit(run_examples(&day_nums, &overrides, timeout));
     }
 
-    if to_run.1 != noop {
-        println!("Running Part 2");
-        let part2_start = Instant::now();
-        to_run.1(input.clone());
-        let part2_dur = part2_start.elapsed();
-        println!("Took {}", fmt_dur(part2_dur));
+    let source = match input_source(&args, &day_nums) {
+        Ok(source) => source,
+        Err(err) => {
+            println!("{}", err);
+            process::exit(1);
+        }
+    };
+    let format = match args.value::<Format>("--format") {
+        // a multi-day run streams its records rather than waiting for one big array
+        Ok(Some(Format::Json)) if day_nums.len() > 1 => Format::Ndjson,
+        Ok(format) => format.unwrap_or(Format::Text),
+        Err(_) => {
+            println!("Invalid value for --format, expected text, json or ndjson");
+            process::exit(1);
+        }
+    };
+
+    let jobs = match args.value::<usize>("--jobs") {
+        // 0 uses every core
+        Ok(Some(0)) => thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+        Ok(jobs) => jobs.unwrap_or(1),
+        Err(err) => {
+            println!("{}", err);
+            process::exit(1);
+        }
+    };
+
+    let history_options = match history_options(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            println!("{}", err);
+            process::exit(1);
+        }
+    };
+
+    if args.has("--profile") {
+        profile::enable();
+    }
+
+    let start = Instant::now();
+    let run = run_days(&day_nums, &source, jobs, &overrides, timeout, true, format);
+    match format {
+        Format::Text if show_summary => {
+            print_summary(&run.results);
+            if jobs > 1 {
+                // the summary adds up per-day times, which overlap when running in parallel
+                println!("Wall time: {} with {} jobs", fmt_dur(start.elapsed()), jobs);
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&run.records).unwrap()),
+        _ => (),
+    }
+    if format == Format::Text && profile::is_enabled() {
+        print_profile(&run.results);
+    }
+    // contended and profiled timings would skew the medians clean runs are compared against
+    let comparable = jobs.min(day_nums.len()) <= 1 && !profile::is_enabled();
+    if let Err(err) = check_history(&history_options, &run, comparable, format) {
+        status(format, &err);
     }
 }
@@ -0,0 +1,80 @@
+use std::borrow::Cow;
+
+const BOM: char = '\u{feff}';
+
+// Cleans up inputs saved on other machines before a day parses them: drops a byte order mark,
+// turns CRLF (and lone CR) line endings into LF, strips whitespace at the end of every line and
+// blank lines at the end of the input. Leading whitespace of each line is stripped too, unless
+// `keep_indent` is set for days where columns matter. Line numbers are unchanged.
+pub fn normalize_input(input: &str, keep_indent: bool) -> Cow<'_, str> {
+    let input = input.strip_prefix(BOM).unwrap_or(input);
+    let unix = input.replace("\r\n", "\n").replace('\r', "\n");
+    let lines = unix
+        .split('\n')
+        .map(|line| if keep_indent { line } else { line.trim_start() }.trim_end())
+        .collect::<Vec<_>>();
+    let len = lines.len()
+        - lines
+            .iter()
+            .rev()
+            .take_while(|line| line.is_empty())
+            .count();
+    let normalized = lines[..len].join("\n");
+
+    if normalized == input {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        days,
+        runner::{example_params, part_fns},
+    };
+
+    #[test]
+    fn test_normalize_input() {
+        assert_eq!(normalize_input("1\n2", false), Cow::Borrowed("1\n2"));
+        assert_eq!(
+            normalize_input("\u{feff}1\r\n 2 \r\n\r\n3\r\n\r\n \n", false),
+            "1\n2\n\n3"
+        );
+        assert_eq!(
+            normalize_input("    [D]    \r\n[N] [C]    \r\n", true),
+            "    [D]\n[N] [C]"
+        );
+        assert_eq!(normalize_input("a\rb", false), "a\nb");
+        assert_eq!(normalize_input("\r\n\r\n", false), "");
+    }
+
+    // Every day's examples, saved with a BOM, CRLF line endings and trailing blank lines
+    #[test]
+    fn test_crlf_examples() {
+        for day in days() {
+            for example in (day.examples)() {
+                let crlf = format!("\u{feff}{}\r\n\r\n", example.input.replace('\n', "\r\n"));
+                let parsed = (day.parse)(example.input).unwrap();
+                let parsed_crlf = (day.parse)(&crlf)
+                    .unwrap_or_else(|err| panic!("day {}: {}", day.number, err.diagnostic()));
+                let params = example_params(&day, &example).unwrap();
+                for (part, part_fn) in part_fns(&day) {
+                    let answer = part_fn(&parsed_crlf, &params);
+                    assert_eq!(
+                        answer,
+                        part_fn(&parsed, &params),
+                        "day {} part {}",
+                        day.number,
+                        part
+                    );
+                    if let Some(expected) = example.expected(part) {
+                        assert_eq!(&answer, expected, "day {} part {}", day.number, part);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::{collections::BTreeMap, convert::TryFrom, fmt};
+
+// A named number a day's parts depend on, overridable with `--param name=value`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+    // smallest value that makes sense, overrides below it are rejected
+    pub min: i64,
+}
+
+impl Param {
+    pub const fn new(name: &'static str, default: i64) -> Param {
+        Param {
+            name,
+            default,
+            min: i64::MIN,
+        }
+    }
+
+    pub const fn min(self, min: i64) -> Param {
+        Param { min, ..self }
+    }
+}
+
+// Values of the parameters a day declares, starting at their defaults
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params {
+    declared: &'static [Param],
+    values: BTreeMap<&'static str, i64>,
+}
+
+impl Params {
+    pub fn new(declared: &'static [Param]) -> Params {
+        Params {
+            declared,
+            values: declared
+                .iter()
+                .map(|param| (param.name, param.default))
+                .collect(),
+        }
+    }
+
+    pub fn declares(&self, name: &str) -> bool {
+        self.declared.iter().any(|param| param.name == name)
+    }
+
+    pub fn set(&mut self, name: &str, value: i64) -> Result<(), String> {
+        let param = self
+            .declared
+            .iter()
+            .find(|param| param.name == name)
+            .ok_or_else(|| format!("Unknown parameter: {}", name))?;
+        if value < param.min {
+            return Err(format!(
+                "Invalid value for parameter {}: {} (at least {})",
+                name, value, param.min
+            ));
+        }
+        self.values.insert(param.name, value);
+        Ok(())
+    }
+
+    // Sets the overrides this day declares, leaving the others to days that do
+    pub fn with_overrides(mut self, overrides: &[(String, i64)]) -> Result<Params, String> {
+        for (name, value) in overrides {
+            if self.declares(name) {
+                self.set(name, *value)?;
+            }
+        }
+        Ok(self)
+    }
+
+    // Panics when `name` is not declared or its value does not fit `T`, the declared minimum
+    // is there to rule the latter out
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> T {
+        let value = *self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("undeclared parameter {}", name));
+        T::try_from(value).unwrap_or_else(|_| panic!("parameter {} out of range: {}", name, value))
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let params = self
+            .declared
+            .iter()
+            .map(|param| format!("{}={}", param.name, self.values[param.name]))
+            .collect::<Vec<_>>();
+        write!(f, "{}", params.join(" "))
+    }
+}
+
+// Parses `name=value`
+pub fn parse_override(arg: &str) -> Result<(String, i64), String> {
+    let (name, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("Expected name=value for --param, got {}", arg))?;
+    let value = value
+        .trim()
+        .replace('_', "")
+        .parse()
+        .map_err(|_| format!("Invalid value for parameter {}: {}", name.trim(), value))?;
+    Ok((String::from(name.trim()), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static DECLARED: &[Param] = &[Param::new("row", 2000000), Param::new("rounds", 20).min(0)];
+
+    #[test]
+    fn test_params() {
+        let mut params = Params::new(DECLARED);
+        assert_eq!(params.get::<isize>("row"), 2000000);
+        assert_eq!(params.to_string(), "row=2000000 rounds=20");
+
+        params.set("row", 10).unwrap();
+        assert_eq!(params.get::<isize>("row"), 10);
+        assert!(params.set("rounds", -1).is_err());
+        assert!(params.set("knots", 2).is_err());
+
+        let params = Params::new(DECLARED)
+            .with_overrides(&[(String::from("rounds"), 5), (String::from("knots"), 2)])
+            .unwrap();
+        assert_eq!(params.get::<usize>("rounds"), 5);
+        assert!(!params.declares("knots"));
+    }
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(
+            parse_override("rounds=10_000"),
+            Ok((String::from("rounds"), 10000))
+        );
+        assert_eq!(parse_override("row = -3"), Ok((String::from("row"), -3)));
+        assert!(parse_override("row").is_err());
+        assert!(parse_override("row=ten").is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use std::{error::Error, fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    // filled in by the runner, parsers don't know which day they belong to
+    pub day: Option<u32>,
+    // 1-based line and column of the offending text
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub line_text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    // For things missing from the input as a whole, points just past its last line
+    pub fn at_end_of(input: &str, message: &str) -> ParseError {
+        let line = lines(input).last().unwrap_or_else(|| Line::new(0, ""));
+        line.error(line.end(), message)
+    }
+
+    pub fn in_day(mut self, day: u32) -> ParseError {
+        self.day = Some(day);
+        self
+    }
+
+    // Multi-line report pointing at the offending text with carets
+    pub fn diagnostic(&self) -> String {
+        let gutter = self.line.to_string().len();
+        format!(
+            "{}\n{:gutter$} |\n{} | {}\n{:gutter$} | {}{}",
+            self,
+            "",
+            self.line,
+            self.line_text,
+            "",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.text.chars().count().max(1)),
+            gutter = gutter
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.day {
+            Some(day) => write!(
+                f,
+                "Parse error in day {}, line {}, column {}: {}",
+                day, self.line, self.column, self.message
+            ),
+            None => write!(
+                f,
+                "Parse error on line {}, column {}: {}",
+                self.line, self.column, self.message
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// A line of puzzle input that knows its position, for building located errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Line<'a> {
+    pub index: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(index: usize, text: &'a str) -> Line<'a> {
+        Line { index, text }
+    }
+
+    // `part` should be a slice of this line, an empty slice points at a position
+    pub fn error(&self, part: &str, message: &str) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + part.len() <= self.text.len())
+            .unwrap_or(0);
+        ParseError {
+            day: None,
+            line: self.index + 1,
+            column: self.text[..offset].chars().count() + 1,
+            text: String::from(part),
+            line_text: String::from(self.text),
+            message: String::from(message),
+        }
+    }
+
+    pub fn end(&self) -> &'a str {
+        &self.text[self.text.len()..]
+    }
+
+    pub fn parse<T: FromStr>(&self, part: &'a str) -> Result<T, ParseError> {
+        part.parse()
+            .map_err(|_| self.error(part, &format!("cannot parse '{}'", part)))
+    }
+
+    pub fn split_once(&self, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(self.end(), &format!("expected '{}'", delimiter)))
+    }
+
+    pub fn strip_prefix(&self, prefix: &str) -> Result<&'a str, ParseError> {
+        self.text
+            .strip_prefix(prefix)
+            .ok_or_else(|| self.error(self.text, &format!("expected '{}'", prefix)))
+    }
+}
+
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, text)| Line::new(index, text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_location() {
+        let line = Line::new(2, "A Q");
+        let error = line.error(&line.text[2..], "unknown shape");
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 3);
+        assert_eq!(error.text, "Q");
+
+        let end = line.error(line.end(), "expected more");
+        assert_eq!(end.column, 4);
+        assert_eq!(end.text, "");
+    }
+
+    #[test]
+    fn test_parse() {
+        let line = Line::new(0, "2-x4");
+        let (from, to) = line.split_once("-").unwrap();
+        assert_eq!(line.parse::<u64>(from), Ok(2));
+        assert_eq!(line.parse::<u64>(to).unwrap_err().column, 3);
+        assert_eq!(line.split_once(",").unwrap_err().column, 5);
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let line = Line::new(9, "move 1 from x to 3");
+        let error = line.error(&line.text[12..13], "cannot parse 'x'").in_day(5);
+        assert_eq!(
+            error.diagnostic(),
+            "Parse error in day 5, line 10, column 13: cannot parse 'x'\n   |\n10 | move 1 from x to 3\n   |             ^"
+        );
+    }
+}
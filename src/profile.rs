@@ -0,0 +1,171 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fs,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+// Counting is off until `enable` is called, so normal runs only pay for one atomic load
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy)]
+struct Counters {
+    allocations: usize,
+    bytes: usize,
+    // can go negative on a thread that frees memory allocated elsewhere
+    live: isize,
+    peak: isize,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+fn update(f: impl FnOnce(&mut Counters)) {
+    if ENABLED.load(Ordering::Relaxed) {
+        // not available while the thread is being torn down, those allocations are not counted
+        let _ = COUNTERS.try_with(|counters| {
+            let mut current = counters.get();
+            f(&mut current);
+            counters.set(current);
+        });
+    }
+}
+
+fn record_alloc(size: usize) {
+    update(|counters| {
+        counters.allocations += 1;
+        counters.bytes += size;
+        counters.live += size as isize;
+        counters.peak = counters.peak.max(counters.live);
+    });
+}
+
+fn record_dealloc(size: usize) {
+    update(|counters| counters.live -= size as isize);
+}
+
+// Global allocator that counts allocations per thread, install it with `#[global_allocator]`
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    // highest amount of memory held at once, above what was live before
+    pub peak_live: usize,
+}
+
+// Allocations made by `f` on the current thread, all zero unless profiling is enabled
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    let before = COUNTERS.with(|counters| {
+        let mut current = counters.get();
+        current.peak = current.live;
+        counters.set(current);
+        current
+    });
+    let result = f();
+    let after = COUNTERS.with(|counters| counters.get());
+
+    let stats = AllocStats {
+        allocations: after.allocations - before.allocations,
+        bytes: after.bytes - before.bytes,
+        peak_live: (after.peak - before.live).max(0) as usize,
+    };
+    (result, stats)
+}
+
+// Peak resident set size of the process in bytes, from `VmHWM` in /proc/self/status
+pub fn peak_rss() -> Option<u64> {
+    parse_peak_rss(&fs::read_to_string("/proc/self/status").ok()?)
+}
+
+fn parse_peak_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kb = line
+        .trim_start_matches("VmHWM:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim();
+    kb.parse::<u64>().ok().map(|kb| kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hint::black_box;
+
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_measure() {
+        enable();
+        let (_, stats) = measure(|| {
+            let first = black_box(vec![0_u8; 1000]);
+            drop(first);
+            black_box(vec![0_u8; 600])
+        });
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.bytes, 1600);
+        assert_eq!(stats.peak_live, 1000);
+
+        let (_, stats) = measure(|| black_box(1 + 1));
+        assert_eq!(stats, AllocStats::default());
+    }
+
+    #[test]
+    fn test_parse_peak_rss() {
+        let status = "Name:\tadvent_of_code\nVmPeak:\t   10000 kB\nVmHWM:\t    2048 kB\n";
+        assert_eq!(parse_peak_rss(status), Some(2048 * 1024));
+        assert_eq!(parse_peak_rss("Name:\tadvent_of_code\n"), None);
+    }
+}
@@ -0,0 +1,90 @@
+use std::convert::TryFrom;
+
+use serde_json::{json, Value};
+
+use crate::{runner::DayResult, Answer};
+
+pub fn answer_to_json(answer: &Answer) -> Value {
+    match answer {
+        // numbers that do not fit JSON consumers' 64-bit integers are given as strings
+        Answer::Number(n) => match i64::try_from(*n) {
+            Ok(n) => Value::from(n),
+            Err(_) => Value::from(n.to_string()),
+        },
+        Answer::Text(text) => Value::from(text.as_str()),
+        Answer::Image(rows) => Value::from(rows.clone()),
+        Answer::Unsolved => Value::Null,
+    }
+}
+
+// One record per executed part, `input` being the path (or "stdin") the input came from
+pub fn part_records(result: &DayResult, input: &str, input_hash: &str) -> Vec<Value> {
+    result
+        .parts
+        .iter()
+        .map(|part| {
+            json!({
+                "day": result.day,
+                "part": part.part,
+                "answer": answer_to_json(&part.answer),
+                "failure": part.failure.as_ref().map(|failure| failure.to_string()),
+                "parse_ns": result.parse_duration.as_nanos() as u64,
+                "solve_ns": part.duration.as_nanos() as u64,
+                "input": input,
+                "input_hash": input_hash,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{profile::AllocStats, runner::PartResult};
+
+    #[test]
+    fn test_answer_to_json() {
+        assert_eq!(answer_to_json(&Answer::Number(-3)), json!(-3));
+        assert_eq!(
+            answer_to_json(&Answer::Number(i128::MAX)),
+            json!(i128::MAX.to_string())
+        );
+        assert_eq!(answer_to_json(&Answer::from("CMZ")), json!("CMZ"));
+        assert_eq!(
+            answer_to_json(&Answer::Image(vec![String::from("#."), String::from(".#")])),
+            json!(["#.", ".#"])
+        );
+        assert_eq!(answer_to_json(&Answer::Unsolved), Value::Null);
+    }
+
+    #[test]
+    fn test_part_records() {
+        let result = DayResult {
+            day: 4,
+            parse_duration: Duration::from_nanos(1500),
+            parse_allocs: AllocStats::default(),
+            parts: vec![PartResult {
+                part: 2,
+                answer: Answer::Number(841),
+                failure: None,
+                duration: Duration::from_micros(3),
+                allocs: AllocStats::default(),
+            }],
+        };
+        assert_eq!(
+            part_records(&result, "inputs/day04.txt", "00ff"),
+            vec![json!({
+                "day": 4,
+                "part": 2,
+                "answer": 841,
+                "failure": null,
+                "parse_ns": 1500,
+                "solve_ns": 3000,
+                "input": "inputs/day04.txt",
+                "input_hash": "00ff",
+            })]
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+    time::Instant,
+};
+
+use crate::{
+    days, get_day,
+    profile::{self, AllocStats},
+    Answer, Day, Example, Params, ParseError, Parsed, PartFn,
+};
+
+pub const LAST_DAY: u32 = 25;
+
+// Why a part has no answer, when run isolated on a worker thread
+#[derive(Debug, Clone, PartialEq)]
+pub enum Failure {
+    Timeout(Duration),
+    Panic { message: String, location: String },
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Timeout(timeout) => write!(f, "TIMEOUT after {:?}", timeout),
+            Failure::Panic { message, location } => write!(f, "PANIC at {}: {}", location, message),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: u32,
+    // `Unsolved` when the part failed
+    pub answer: Answer,
+    pub failure: Option<Failure>,
+    pub duration: Duration,
+    // all zero unless profiling is enabled
+    pub allocs: AllocStats,
+}
+
+impl PartResult {
+    pub fn failed(part: u32, failure: Failure, duration: Duration) -> PartResult {
+        PartResult {
+            part,
+            answer: Answer::Unsolved,
+            failure: Some(failure),
+            duration,
+            allocs: AllocStats::default(),
+        }
+    }
+
+    // The answer, or why there is none
+    pub fn outcome(&self) -> String {
+        match &self.failure {
+            Some(failure) => failure.to_string(),
+            None => self.answer.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayResult {
+    pub day: u32,
+    pub parse_duration: Duration,
+    pub parse_allocs: AllocStats,
+    pub parts: Vec<PartResult>,
+}
+
+impl DayResult {
+    pub fn total_duration(&self) -> Duration {
+        self.parts
+            .iter()
+            .fold(self.parse_duration, |acc, part| acc + part.duration)
+    }
+}
+
+pub fn registered_days() -> Vec<u32> {
+    days().iter().map(|day| day.number).collect()
+}
+
+// Parts that are registered with a solver
+pub fn implemented_parts(day: &Day) -> Vec<u32> {
+    part_fns(day).into_iter().map(|(part, _)| part).collect()
+}
+
+// Accepts a single day ("12"), an inclusive range ("3-9") or "all"
+pub fn parse_day_selection(selection: &str) -> Option<Vec<u32>> {
+    let selection = selection.trim();
+    if selection == "all" {
+        return Some(registered_days());
+    }
+    match selection.split_once('-') {
+        Some((from, to)) => {
+            let from: u32 = from.trim().parse().ok()?;
+            let to: u32 = to.trim().parse().ok()?;
+            if from > to {
+                return None;
+            }
+            Some((from..=to).filter(|day| get_day(*day).is_some()).collect())
+        }
+        None => selection.parse().ok().map(|day| vec![day]),
+    }
+}
+
+// Parts to run for a day, skipping unimplemented ones
+pub(crate) fn part_fns(day: &Day) -> Vec<(u32, PartFn)> {
+    [(1, day.part1), (2, day.part2)]
+        .iter()
+        .filter_map(|&(part, part_fn)| Some((part, part_fn?)))
+        .collect()
+}
+
+pub(crate) fn timed_parse(
+    day: &Day,
+    input: &str,
+) -> (Result<Parsed, ParseError>, Duration, AllocStats) {
+    let ((parsed, duration), allocs) = profile::measure(|| {
+        let parse_start = Instant::now();
+        let parsed = (day.parse)(input);
+        (parsed, parse_start.elapsed())
+    });
+    (parsed, duration, allocs)
+}
+
+pub(crate) fn timed_part(
+    part: u32,
+    part_fn: PartFn,
+    parsed: &Parsed,
+    params: &Params,
+) -> PartResult {
+    let ((answer, duration), allocs) = profile::measure(|| {
+        let part_start = Instant::now();
+        let answer = part_fn(parsed, params);
+        (answer, part_start.elapsed())
+    });
+    PartResult {
+        part,
+        answer,
+        failure: None,
+        duration,
+        allocs,
+    }
+}
+
+pub fn run_day(
+    day_num: u32,
+    day: &Day,
+    input: &str,
+    params: &Params,
+) -> Result<DayResult, ParseError> {
+    let (parsed, parse_duration, parse_allocs) = timed_parse(day, input);
+    let parsed = parsed.map_err(|err| err.in_day(day_num))?;
+
+    let parts = part_fns(day)
+        .into_iter()
+        .map(|(part, part_fn)| timed_part(part, part_fn, &parsed, params))
+        .collect();
+
+    Ok(DayResult {
+        day: day_num,
+        parse_duration,
+        parse_allocs,
+        parts,
+    })
+}
+
+// The day's default parameters with the ones the example is sized for on top
+pub fn example_params(day: &Day, example: &Example) -> Result<Params, String> {
+    let mut params = Params::new(day.params);
+    for (name, value) in &example.params {
+        params.set(name, *value)?;
+    }
+    Ok(params)
+}
+
+// Runs `job` for every item on up to `jobs` threads and hands the results to `report` in
+// the order of `items`, as soon as all earlier ones are in. A single job runs on this thread.
+pub fn run_ordered<T, R, J, F>(items: &[T], jobs: usize, job: J, mut report: F)
+where
+    T: Sync,
+    R: Send,
+    J: Fn(&T) -> R + Sync,
+    F: FnMut(R),
+{
+    if jobs <= 1 {
+        items.iter().for_each(|item| report(job(item)));
+        return;
+    }
+
+    let next_item = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let (next_item, job, sender) = (&next_item, &job, sender.clone());
+            scope.spawn(move || loop {
+                let index = next_item.fetch_add(1, Ordering::Relaxed);
+                if index >= items.len() || sender.send((index, job(&items[index]))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_report = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_report) {
+                report(result);
+                next_report += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_day_selection() {
+        assert_eq!(parse_day_selection("12"), Some(vec![12]));
+        assert_eq!(parse_day_selection("3-5"), Some(vec![3, 4, 5]));
+        assert_eq!(parse_day_selection("all"), Some(registered_days()));
+        assert_eq!(parse_day_selection("9-3"), None);
+        assert_eq!(parse_day_selection("twelve"), None);
+    }
+
+    #[test]
+    fn test_registered_days() {
+        let day_nums = registered_days();
+        assert!(day_nums.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(day_nums.iter().all(|day| (1..=LAST_DAY).contains(day)));
+        assert_eq!(implemented_parts(&get_day(1).unwrap()), vec![1, 2]);
+        assert!(implemented_parts(&get_day(16).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_run_ordered() {
+        let items = (0..20).collect::<Vec<u64>>();
+        for jobs in [1, 4] {
+            let mut reported = vec![];
+            run_ordered(
+                &items,
+                jobs,
+                |item| {
+                    // later items finish first
+                    thread::sleep(Duration::from_millis(20 - item));
+                    item * 2
+                },
+                |result| reported.push(result),
+            );
+            assert_eq!(
+                reported,
+                items.iter().map(|item| item * 2).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        for day_num in registered_days() {
+            let day = get_day(day_num).unwrap();
+            for example in (day.examples)() {
+                let params = example_params(&day, &example).unwrap();
+                for part in run_day(day_num, &day, example.input, &params)
+                    .unwrap()
+                    .parts
+                {
+                    if let Some(expected) = example.expected(part.part) {
+                        assert_eq!(
+                            part.answer.to_string(),
+                            expected.to_string(),
+                            "day {} part {}",
+                            day_num,
+                            part.part
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_day() {
+        let day = get_day(1).unwrap();
+        let result = run_day(1, &day, "1000\n2000\n\n4000\n\n500", &Params::new(&[])).unwrap();
+        assert_eq!(result.day, 1);
+        assert_eq!(
+            result
+                .parts
+                .iter()
+                .map(|part| (part.part, part.answer.clone()))
+                .collect::<Vec<_>>(),
+            vec![(1, Answer::Number(4000)), (2, Answer::Number(7500))]
+        );
+
+        let error = run_day(1, &day, "1000\nlots", &Params::new(&[])).unwrap_err();
+        assert_eq!(error.day, Some(1));
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+static TEMPLATE: &str = r#"use crate::{parse_error::lines, Answer, Example, Params, ParseError, Solution};
+
+pub(crate) struct Day{{padded}};
+
+impl Solution for Day{{padded}} {
+    const DAY: u32 = {{day}};
+    const TITLE: &'static str = {{title}};
+    const PARTS: u32 = 0;
+
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(lines: &Self::Parsed, _params: &Params) -> Answer {
+        part1_impl(lines).into()
+    }
+
+    fn part2(lines: &Self::Parsed, _params: &Params) -> Answer {
+        part2_impl(lines).into()
+    }
+
+    fn examples() -> Vec<Example> {
+        vec![Example::new(EXAMPLE_INPUT)]
+    }
+}
+
+fn parse_lines(input: &str) -> Result<Vec<String>, ParseError> {
+    Ok(lines(input).map(|line| String::from(line.text)).collect())
+}
+
+fn part1_impl(_lines: &[String]) -> usize {
+    0
+}
+
+fn part2_impl(_lines: &[String]) -> usize {
+    0
+}
+
+static EXAMPLE_INPUT: &str = "";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2_impl(&parse_lines(EXAMPLE_INPUT).unwrap()), 0);
+    }
+}
+"#;
+
+const REGISTRY_START: &str = "register_days! {\n";
+
+pub fn module_name(day: u32) -> String {
+    format!("day{:02}", day)
+}
+
+pub fn day_source(day: u32, title: &str) -> String {
+    TEMPLATE
+        .replace("{{padded}}", &format!("{:02}", day))
+        .replace("{{day}}", &day.to_string())
+        .replace("{{title}}", &format!("{:?}", title))
+}
+
+// Adds `dayNN::DayNN,` to the `register_days!` list in lib.rs, keeping it in day order
+pub fn register(lib_source: &str, day: u32) -> Result<String, String> {
+    let module = module_name(day);
+    let start = lib_source
+        .find(REGISTRY_START)
+        .ok_or("Could not find the register_days! list")?
+        + REGISTRY_START.len();
+    let end = start
+        + lib_source[start..]
+            .find('}')
+            .ok_or("Could not find the end of the register_days! list")?;
+
+    let mut insert_at = end;
+    let mut offset = start;
+    for line in lib_source[start..end].split_inclusive('\n') {
+        let registered = line.trim().split("::").next().unwrap_or("");
+        if registered == module {
+            return Err(format!("Day {} is already registered", day));
+        }
+        if registered > module.as_str() {
+            insert_at = offset;
+            break;
+        }
+        offset += line.len();
+    }
+
+    Ok(format!(
+        "{}    {}::Day{:02},\n{}",
+        &lib_source[..insert_at],
+        module,
+        day,
+        &lib_source[insert_at..]
+    ))
+}
+
+fn ensure_missing(path: &Path) -> io::Result<()> {
+    if path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", path.display()),
+        ));
+    }
+    Ok(())
+}
+
+// Creates src/dayNN.rs and an empty inputs/dayNN.txt under `root` and registers the day,
+// returning the files it touched. Nothing is written if any of them would be overwritten.
+pub fn new_day(root: &Path, day: u32, title: &str) -> io::Result<Vec<PathBuf>> {
+    let module_path = root.join("src").join(format!("{}.rs", module_name(day)));
+    let input_path = root
+        .join("inputs")
+        .join(format!("{}.txt", module_name(day)));
+    let lib_path = root.join("src").join("lib.rs");
+
+    ensure_missing(&module_path)?;
+    ensure_missing(&input_path)?;
+    let lib_source = register(&fs::read_to_string(&lib_path)?, day)
+        .map_err(|err| io::Error::new(io::ErrorKind::AlreadyExists, err))?;
+
+    fs::write(&module_path, day_source(day, title))?;
+    fs::write(&input_path, "")?;
+    fs::write(&lib_path, lib_source)?;
+    Ok(vec![module_path, input_path, lib_path])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_LIB: &str =
+        "register_days! {\n    day01::Day01,\n    day03::Day03,\n}\n\npub fn get_day() {}\n";
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            register(TEST_LIB, 2).unwrap(),
+            "register_days! {\n    day01::Day01,\n    day02::Day02,\n    day03::Day03,\n}\n\npub fn get_day() {}\n"
+        );
+        assert_eq!(
+            register(TEST_LIB, 12).unwrap(),
+            "register_days! {\n    day01::Day01,\n    day03::Day03,\n    day12::Day12,\n}\n\npub fn get_day() {}\n"
+        );
+        assert!(register(TEST_LIB, 3).is_err());
+        assert!(register("pub fn get_day() {}", 3).is_err());
+    }
+
+    #[test]
+    fn test_day_source() {
+        let source = day_source(7, "No \"Space\"");
+        assert!(source.contains("pub(crate) struct Day07;"));
+        assert!(source.contains("const DAY: u32 = 7;"));
+        assert!(source.contains(r#"const TITLE: &'static str = "No \"Space\"";"#));
+        assert!(!source.contains("{{"));
+    }
+}
@@ -0,0 +1,406 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use num::Zero;
+
+use crate::grid::{Grid, Position};
+
+// What a search keeps about each state it reached. A `HashMap` takes any state, a grid of
+// `Option`s holds positions in a flat `Vec` and is much faster for searches across a grid.
+pub trait Store<S, V> {
+    fn get(&self, state: &S) -> Option<&V>;
+    fn insert(&mut self, state: S, value: V);
+}
+
+impl<S: Eq + Hash, V> Store<S, V> for HashMap<S, V> {
+    fn get(&self, state: &S) -> Option<&V> {
+        HashMap::get(self, state)
+    }
+
+    fn insert(&mut self, state: S, value: V) {
+        HashMap::insert(self, state, value);
+    }
+}
+
+// Sized like the grid searched, positions outside it are never reached
+impl<V> Store<Position, V> for Grid<Option<V>> {
+    fn get(&self, &position: &Position) -> Option<&V> {
+        Grid::get(self, position)?.as_ref()
+    }
+
+    fn insert(&mut self, position: Position, value: V) {
+        self[position] = Some(value);
+    }
+}
+
+// The states from where a search started to the goal it found, both included, and what getting
+// there cost
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub states: Vec<S>,
+    pub cost: C,
+}
+
+// Follows `parent` back from `goal` to a state without one, a start
+fn reconstruct<S>(goal: S, mut parent: impl FnMut(&S) -> Option<S>) -> Vec<S> {
+    let mut states = vec![goal];
+    while let Some(previous) = parent(states.last().unwrap()) {
+        states.push(previous);
+    }
+    states.reverse();
+    states
+}
+
+// Fewest steps from any of `starts` to a state `is_goal` accepts, `neighbours` giving the states
+// one step away. Neighbours are tried in the order given, so among paths equally short the one
+// through earlier neighbours wins.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    bfs_with(HashMap::new(), starts, neighbours, is_goal)
+}
+
+// `bfs` keeping where each state was reached from in `parents`, which starts out empty
+pub fn bfs_with<S, I>(
+    mut parents: impl Store<S, Option<S>>,
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if parents.get(&start).is_none() {
+            parents.insert(start.clone(), None);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, steps)) = queue.pop_front() {
+        if is_goal(&state) {
+            return Some(Path {
+                states: reconstruct(state, |state| parents.get(state).cloned().flatten()),
+                cost: steps,
+            });
+        }
+        for next in neighbours(&state) {
+            if parents.get(&next).is_none() {
+                parents.insert(next.clone(), Some(state.clone()));
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+// Cheapest path from any of `starts` to a state `is_goal` accepts, `neighbours` giving the
+// states one step away with what that step costs. `heuristic` estimates the cost left to a
+// goal, it must never overestimate for the path to be the cheapest.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_with(HashMap::new(), starts, neighbours, heuristic, is_goal)
+}
+
+// `astar` keeping the cheapest known cost of reaching each state and where that came from in
+// `best`, which starts out empty
+pub fn astar_with<S, C, I>(
+    mut best: impl Store<S, (C, Option<S>)>,
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    // the heap holds indices into `queued`, so states need no ordering of their own, and the
+    // index breaks ties between equal estimates in the order states were queued
+    let mut queued = vec![];
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if best.get(&start).is_none() {
+            best.insert(start.clone(), (C::zero(), None));
+            heap.push(Reverse((heuristic(&start), C::zero(), queued.len())));
+            queued.push(start);
+        }
+    }
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let state = queued[index].clone();
+        // a stale entry, the state has been queued again with a lower cost since
+        if best
+            .get(&state)
+            .is_some_and(|&(known_cost, _)| known_cost < cost)
+        {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(Path {
+                states: reconstruct(state, |state| {
+                    best.get(state).and_then(|(_, parent)| parent.clone())
+                }),
+                cost,
+            });
+        }
+        for (next, step_cost) in neighbours(&state) {
+            let next_cost = cost + step_cost;
+            if best
+                .get(&next)
+                .is_none_or(|&(known_cost, _)| next_cost < known_cost)
+            {
+                best.insert(next.clone(), (next_cost, Some(state.clone())));
+                heap.push(Reverse((
+                    next_cost + heuristic(&next),
+                    next_cost,
+                    queued.len(),
+                )));
+                queued.push(next);
+            }
+        }
+    }
+    None
+}
+
+// `astar` without a heuristic
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    dijkstra_with(HashMap::new(), starts, neighbours, is_goal)
+}
+
+// `astar_with` without a heuristic
+pub fn dijkstra_with<S, C, I>(
+    best: impl Store<S, (C, Option<S>)>,
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone,
+    C: Copy + Ord + Add<Output = C> + Zero,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_with(best, starts, neighbours, |_| C::zero(), is_goal)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    // '#' is a wall, digits cost that much to step onto and anything else costs 1
+    fn maze(rows: &[&str]) -> Grid<char> {
+        Grid::parse(&rows.join("\n"), Some, "").unwrap()
+    }
+
+    fn open(maze: &Grid<char>, position: Position) -> Vec<Position> {
+        maze.neighbours4(position)
+            .filter(|&next| maze[next] != '#')
+            .collect()
+    }
+
+    fn weighted(maze: &Grid<char>, position: Position) -> Vec<(Position, u32)> {
+        open(maze, position)
+            .into_iter()
+            .map(|next| (next, maze[next].to_digit(10).unwrap_or(1)))
+            .collect()
+    }
+
+    fn manhattan((x1, y1): Position, (x2, y2): Position) -> u32 {
+        (x1.abs_diff(x2) + y1.abs_diff(y2)) as u32
+    }
+
+    #[test]
+    fn test_bfs() {
+        let maze = maze(&["..#.", ".##.", "...."]);
+        let path = bfs([(0, 0)], |&p| open(&maze, p), |&p| p == (3, 0)).unwrap();
+        assert_eq!(path.cost, 7);
+        assert_eq!(
+            path.states,
+            vec![
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 2),
+                (2, 2),
+                (3, 2),
+                (3, 1),
+                (3, 0)
+            ]
+        );
+        assert_eq!(
+            bfs([(0, 0)], |&p| open(&maze, p), |&p| p == (0, 0)),
+            Some(Path {
+                states: vec![(0, 0)],
+                cost: 0
+            })
+        );
+        assert_eq!(bfs([(0, 0)], |&p| open(&maze, p), |&p| p == (2, 0)), None);
+    }
+
+    #[test]
+    fn test_multi_source() {
+        let maze = maze(&["..#.", ".##.", "...."]);
+        let path = bfs([(0, 0), (3, 2)], |&p| open(&maze, p), |&p| p == (3, 0)).unwrap();
+        assert_eq!(path.states, vec![(3, 2), (3, 1), (3, 0)]);
+        assert_eq!(bfs(vec![], |&p| open(&maze, p), |_| true), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // going round the nines is cheaper than through them
+        let maze = maze(&["...", "99.", "..."]);
+        let path = dijkstra([(0, 0)], |&p| weighted(&maze, p), |&p| p == (0, 2)).unwrap();
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.states.len(), 7);
+        let path = dijkstra([(0, 0)], |&p| weighted(&maze, p), |&p| p == (1, 2)).unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(
+            dijkstra([(0, 0)], |&p| weighted(&maze, p), |&p| p == (3, 3)),
+            None::<Path<Position, u32>>
+        );
+    }
+
+    #[test]
+    fn test_astar() {
+        let maze = maze(&["....#...", ".##.#.#.", ".#..#.#.", ".#.##.#.", "......#."]);
+        let goal = (7, 0);
+        let path = astar(
+            [(0, 0)],
+            |&p| weighted(&maze, p),
+            |&p| manhattan(p, goal),
+            |&p| p == goal,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.states.first(), Some(&(0, 0)));
+        assert_eq!(path.states.last(), Some(&goal));
+    }
+
+    // Nothing reached yet on any cell of `maze`
+    fn store<V: Clone>(maze: &Grid<char>) -> Grid<Option<V>> {
+        Grid::new(maze.width(), maze.height(), None)
+    }
+
+    #[test]
+    fn test_grid_store() {
+        let maze = maze(&["..#.", ".##.", "...."]);
+        let path = bfs_with(
+            store(&maze),
+            [(0, 0)],
+            |&p| open(&maze, p),
+            |&p| p == (3, 0),
+        );
+        assert_eq!(path, bfs([(0, 0)], |&p| open(&maze, p), |&p| p == (3, 0)));
+        assert_eq!(path.map(|path| path.cost), Some(7));
+        let path = dijkstra_with(
+            store(&maze),
+            [(0, 0)],
+            |&p| weighted(&maze, p),
+            |&p| p == (3, 0),
+        );
+        assert_eq!(path.map(|path| path.cost), Some(7));
+        assert_eq!(
+            bfs_with(
+                store(&maze),
+                [(0, 0)],
+                |&p| open(&maze, p),
+                |&p| p == (2, 0)
+            ),
+            None
+        );
+    }
+
+    // Mazes of walls and costly cells on a small grid
+    fn weighted_maze() -> impl Strategy<Value = Grid<char>> {
+        (2..8_usize, 2..8_usize).prop_flat_map(|(width, height)| {
+            prop::collection::vec(
+                prop::sample::select(vec!['.', '.', '.', '#', '2', '5']),
+                width * height,
+            )
+            .prop_map(move |cells| {
+                Grid::from_rows(cells.chunks(width).map(|row| row.to_vec()).collect()).unwrap()
+            })
+            .prop_filter("starts and ends open", move |maze| {
+                maze[(0, 0)] != '#' && maze[(width - 1, height - 1)] != '#'
+            })
+        })
+    }
+
+    proptest! {
+        // each search finds a path exactly when the others do, paths are made of valid steps
+        // and add up to their cost, and the cheapest one costs no more than the shortest
+        #[test]
+        fn searches_agree(maze in weighted_maze()) {
+            let goal = (maze.width() - 1, maze.height() - 1);
+            let shortest = bfs([(0, 0)], |&p| open(&maze, p), |&p| p == goal);
+            let cheapest = dijkstra([(0, 0)], |&p| weighted(&maze, p), |&p| p == goal);
+            let guided = astar(
+                [(0, 0)],
+                |&p| weighted(&maze, p),
+                |&p| manhattan(p, goal),
+                |&p| p == goal,
+            );
+            prop_assert_eq!(
+                bfs_with(store(&maze), [(0, 0)], |&p| open(&maze, p), |&p| p == goal),
+                shortest.clone()
+            );
+            prop_assert_eq!(
+                dijkstra_with(store(&maze), [(0, 0)], |&p| weighted(&maze, p), |&p| p == goal),
+                cheapest.clone()
+            );
+            prop_assert_eq!(shortest.is_some(), cheapest.is_some());
+            prop_assert_eq!(
+                cheapest.as_ref().map(|path| path.cost),
+                guided.as_ref().map(|path| path.cost)
+            );
+            if let (Some(shortest), Some(cheapest)) = (shortest, cheapest) {
+                prop_assert_eq!(shortest.states.len(), shortest.cost + 1);
+                let steps = cheapest
+                    .states
+                    .windows(2)
+                    .map(|step| {
+                        weighted(&maze, step[0])
+                            .into_iter()
+                            .find(|&(next, _)| next == step[1])
+                            .map(|(_, cost)| cost)
+                    })
+                    .collect::<Option<Vec<_>>>();
+                prop_assert_eq!(steps.map(|steps| steps.iter().sum::<u32>()), Some(cheapest.cost));
+                prop_assert!(cheapest.cost as usize >= shortest.cost);
+            }
+        }
+    }
+}
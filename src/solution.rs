@@ -0,0 +1,133 @@
+use std::any::Any;
+
+use crate::{
+    generate::Rng,
+    normalize::normalize_input,
+    params::{Param, Params},
+    Answer, ParseError,
+};
+
+pub trait Solution {
+    const DAY: u32;
+    const TITLE: &'static str;
+    // Parts 1 up to `PARTS` are implemented, later ones are registered as `None`
+    const PARTS: u32 = 2;
+    // Whether leading whitespace on each line is kept when the input is normalized
+    const KEEP_INDENT: bool = false;
+    // Puzzle constants the parts read from their `Params`
+    const PARAMS: &'static [Param] = &[];
+
+    type Parsed: 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+
+    fn part1(_parsed: &Self::Parsed, _params: &Params) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(_parsed: &Self::Parsed, _params: &Params) -> Answer {
+        Answer::Unsolved
+    }
+
+    // Example inputs from the puzzle text, to sanity-check a day before the real input
+    fn examples() -> Vec<Example> {
+        vec![]
+    }
+
+    // A random valid input of roughly `size` (lines, directories, monkeys, ... as the day
+    // documents) for stress tests, `None` for days without a generator
+    fn generate(_rng: &mut Rng, _size: usize, _params: &Params) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub input: &'static str,
+    // `None` when the puzzle text gives no answer for that part
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    // parameters the example is sized for, when they differ from the real input's
+    pub params: Vec<(String, i64)>,
+}
+
+impl Example {
+    pub fn new(input: &'static str) -> Example {
+        Example {
+            input,
+            part1: None,
+            part2: None,
+            params: vec![],
+        }
+    }
+
+    pub fn part1(mut self, answer: impl Into<Answer>) -> Example {
+        self.part1 = Some(answer.into());
+        self
+    }
+
+    pub fn part2(mut self, answer: impl Into<Answer>) -> Example {
+        self.part2 = Some(answer.into());
+        self
+    }
+
+    pub fn param(mut self, name: &str, value: i64) -> Example {
+        self.params.push((String::from(name), value));
+        self
+    }
+
+    pub fn expected(&self, part: u32) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+// Parsed input with its type erased, so every day fits behind the same function pointers
+pub type Parsed = Box<dyn Any>;
+
+pub type ParseFn = fn(&str) -> Result<Parsed, ParseError>;
+pub type PartFn = fn(&Parsed, &Params) -> Answer;
+pub type GenerateFn = fn(&mut Rng, usize, &Params) -> Option<String>;
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u32,
+    pub title: &'static str,
+    pub parse: ParseFn,
+    // `None` for parts the solution does not implement yet
+    pub part1: Option<PartFn>,
+    pub part2: Option<PartFn>,
+    pub params: &'static [Param],
+    pub examples: fn() -> Vec<Example>,
+    pub generate: GenerateFn,
+}
+
+impl Day {
+    pub fn from_solution<S: Solution>() -> Day {
+        Day {
+            number: S::DAY,
+            title: S::TITLE,
+            parse: parse::<S>,
+            part1: (S::PARTS >= 1).then_some(part1::<S> as PartFn),
+            part2: (S::PARTS >= 2).then_some(part2::<S> as PartFn),
+            params: S::PARAMS,
+            examples: S::examples,
+            generate: S::generate,
+        }
+    }
+}
+
+fn parse<S: Solution>(input: &str) -> Result<Parsed, ParseError> {
+    Ok(Box::new(S::parse(&normalize_input(input, S::KEEP_INDENT))?))
+}
+
+fn part1<S: Solution>(parsed: &Parsed, params: &Params) -> Answer {
+    S::part1(parsed.downcast_ref().unwrap(), params)
+}
+
+fn part2<S: Solution>(parsed: &Parsed, params: &Params) -> Answer {
+    S::part2(parsed.downcast_ref().unwrap(), params)
+}
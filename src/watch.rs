@@ -0,0 +1,114 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+// What is checked to tell a file changed, `None` while it does not exist
+type FileState = Option<(SystemTime, u64)>;
+
+fn file_state(path: &Path) -> FileState {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+// Polls files for changes to their modification time or length
+pub struct Watcher {
+    files: Vec<(PathBuf, FileState)>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        Watcher {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let state = file_state(&path);
+                    (path, state)
+                })
+                .collect(),
+        }
+    }
+
+    // Files that changed, appeared or disappeared since the last call
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        self.files
+            .iter_mut()
+            .filter_map(|(path, state)| {
+                let current = file_state(path);
+                if current == *state {
+                    None
+                } else {
+                    *state = current;
+                    Some(path.clone())
+                }
+            })
+            .collect()
+    }
+}
+
+// Compares labelled answers of two runs, one line per label of the current run
+pub fn diff_answers(previous: &[(String, String)], current: &[(String, String)]) -> Vec<String> {
+    current
+        .iter()
+        .map(|(label, answer)| {
+            match previous
+                .iter()
+                .find(|(previous_label, _)| previous_label == label)
+            {
+                Some((_, previous_answer)) if previous_answer == answer => {
+                    format!("{}: {} (unchanged)", label, answer)
+                }
+                Some((_, previous_answer)) => {
+                    format!("{}: {} (was {})", label, answer, previous_answer)
+                }
+                None => format!("{}: {} (new)", label, answer),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    #[test]
+    fn test_watcher() {
+        let path = env::temp_dir().join(format!("watch-test-{}.txt", process::id()));
+        fs::remove_file(&path).ok();
+        let mut watcher = Watcher::new(vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&path, "1").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&path, "12").unwrap();
+        assert_eq!(watcher.changed(), vec![path.clone()]);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(watcher.changed(), vec![path]);
+    }
+
+    #[test]
+    fn test_diff_answers() {
+        let answer = |label: &str, answer: &str| (String::from(label), String::from(answer));
+        assert_eq!(
+            diff_answers(
+                &[answer("Part 1", "24000"), answer("Part 2", "41000")],
+                &[
+                    answer("Part 1", "24000"),
+                    answer("Part 2", "45000"),
+                    answer("Example 1 part 1", "24000")
+                ]
+            ),
+            vec![
+                "Part 1: 24000 (unchanged)",
+                "Part 2: 45000 (was 41000)",
+                "Example 1 part 1: 24000 (new)"
+            ]
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Once,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    profile::AllocStats,
+    runner::{part_fns, timed_parse, timed_part, DayResult, Failure, PartResult},
+    Day, Params, ParseError, PartFn,
+};
+
+// Panics on threads named like this are reported in the results instead of on stderr
+const WORKER_PREFIX: &str = "runner-";
+
+thread_local! {
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn install_panic_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let is_worker = thread::current()
+                .name()
+                .is_some_and(|name| name.starts_with(WORKER_PREFIX));
+            if is_worker {
+                let location = info.location().map_or_else(
+                    || String::from("unknown location"),
+                    |location| location.to_string(),
+                );
+                PANIC_LOCATION.with(|cell| *cell.borrow_mut() = Some(location));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+fn panic_failure(payload: Box<dyn Any + Send>) -> Failure {
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        String::from(*message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("non-string panic payload")
+    };
+    Failure::Panic {
+        message,
+        location: PANIC_LOCATION
+            .with(|cell| cell.borrow_mut().take())
+            .unwrap_or_else(|| String::from("unknown location")),
+    }
+}
+
+fn catch<R>(f: impl FnOnce() -> R) -> Result<R, Failure> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_failure)
+}
+
+enum Event {
+    Parsed(Duration, AllocStats),
+    ParseError(ParseError),
+    ParsePanicked(Failure),
+    Part(PartResult),
+}
+
+// Parses the input and runs `parts` in order, reporting after every step. Parsed input is
+// not `Send`, so it stays on this thread.
+fn spawn_worker(
+    day_num: u32,
+    day: Day,
+    input: String,
+    params: Params,
+    parts: Vec<(u32, PartFn)>,
+) -> Receiver<Event> {
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .name(format!("{}day{:02}", WORKER_PREFIX, day_num))
+        .spawn(move || {
+            let parsed = match catch(|| timed_parse(&day, &input)) {
+                Ok((Ok(parsed), duration, allocs)) => {
+                    sender.send(Event::Parsed(duration, allocs)).ok();
+                    parsed
+                }
+                Ok((Err(err), _, _)) => {
+                    sender.send(Event::ParseError(err)).ok();
+                    return;
+                }
+                Err(failure) => {
+                    sender.send(Event::ParsePanicked(failure)).ok();
+                    return;
+                }
+            };
+            for (part, part_fn) in parts {
+                let start = Instant::now();
+                let result = catch(|| timed_part(part, part_fn, &parsed, &params))
+                    .unwrap_or_else(|failure| PartResult::failed(part, failure, start.elapsed()));
+                if sender.send(Event::Part(result)).is_err() {
+                    return;
+                }
+            }
+        })
+        .expect("failed to spawn worker thread");
+    receiver
+}
+
+fn wait(receiver: &Receiver<Event>, timeout: Option<Duration>) -> Result<Event, Failure> {
+    let event = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    event.map_err(|err| match err {
+        RecvTimeoutError::Timeout => Failure::Timeout(timeout.unwrap_or_default()),
+        RecvTimeoutError::Disconnected => Failure::Panic {
+            message: String::from("worker thread stopped without a result"),
+            location: String::from("unknown location"),
+        },
+    })
+}
+
+// Like `run_day`, but parsing and each part run on a worker thread so a panic or a step
+// running longer than `timeout` becomes a failed part instead of taking the runner down.
+// A timed out worker cannot be stopped, it is left behind and the remaining parts get a
+// fresh worker.
+pub fn run_day_isolated(
+    day_num: u32,
+    day: &Day,
+    input: &str,
+    params: &Params,
+    timeout: Option<Duration>,
+) -> Result<DayResult, ParseError> {
+    install_panic_hook();
+
+    let mut remaining = part_fns(day);
+    let mut parse = None;
+    let mut parts = vec![];
+    while parse.is_none() || !remaining.is_empty() {
+        let start = Instant::now();
+        let receiver = spawn_worker(
+            day_num,
+            *day,
+            String::from(input),
+            params.clone(),
+            remaining.clone(),
+        );
+        match wait(&receiver, timeout) {
+            Ok(Event::Parsed(duration, allocs)) => {
+                parse.get_or_insert((duration, allocs));
+            }
+            Ok(Event::ParseError(err)) => return Err(err.in_day(day_num)),
+            Ok(Event::ParsePanicked(failure)) | Err(failure) => {
+                // without parsed input no part can run
+                parse.get_or_insert((start.elapsed(), AllocStats::default()));
+                parts.extend(
+                    remaining
+                        .drain(..)
+                        .map(|(part, _)| PartResult::failed(part, failure.clone(), Duration::ZERO)),
+                );
+                break;
+            }
+            Ok(Event::Part(_)) => unreachable!("parts are only run after parsing"),
+        }
+
+        while let Some(&(part, _)) = remaining.first() {
+            let start = Instant::now();
+            let result = match wait(&receiver, timeout) {
+                Ok(Event::Part(result)) => result,
+                Ok(_) => unreachable!("parsing only happens once per worker"),
+                Err(failure) => PartResult::failed(part, failure, start.elapsed()),
+            };
+            remaining.remove(0);
+            let timed_out = matches!(result.failure, Some(Failure::Timeout(_)));
+            parts.push(result);
+            if timed_out {
+                break;
+            }
+        }
+    }
+
+    let (parse_duration, parse_allocs) = parse.unwrap_or_default();
+    Ok(DayResult {
+        day: day_num,
+        parse_duration,
+        parse_allocs,
+        parts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_day, Answer, Parsed};
+
+    fn hang(_parsed: &Parsed, _params: &Params) -> Answer {
+        loop {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn explode(_parsed: &Parsed, _params: &Params) -> Answer {
+        panic!("boom")
+    }
+
+    #[test]
+    fn test_run_day_isolated() {
+        let day = get_day(1).unwrap();
+        let result = run_day_isolated(
+            1,
+            &day,
+            "1000\n2000\n\n4000\n\n500",
+            &Params::new(&[]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            result
+                .parts
+                .iter()
+                .map(|part| (part.part, part.answer.clone(), part.failure.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, Answer::Number(4000), None),
+                (2, Answer::Number(7500), None)
+            ]
+        );
+
+        let error = run_day_isolated(1, &day, "1000\nlots", &Params::new(&[]), None).unwrap_err();
+        assert_eq!(error.day, Some(1));
+    }
+
+    #[test]
+    fn test_panic() {
+        let day = Day {
+            part1: Some(explode),
+            ..get_day(1).unwrap()
+        };
+        let result = run_day_isolated(
+            1,
+            &day,
+            "1000\n2000\n\n4000\n\n500",
+            &Params::new(&[]),
+            None,
+        )
+        .unwrap();
+        match &result.parts[0].failure {
+            Some(Failure::Panic { message, location }) => {
+                assert_eq!(message, "boom");
+                assert!(location.contains("worker.rs"));
+            }
+            failure => panic!("expected a panic, got {:?}", failure),
+        }
+        assert_eq!(result.parts[1].answer, Answer::Number(7500));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let day = Day {
+            part1: Some(hang),
+            ..get_day(1).unwrap()
+        };
+        let timeout = Duration::from_millis(50);
+        let result = run_day_isolated(
+            1,
+            &day,
+            "1000\n2000\n\n4000\n\n500",
+            &Params::new(&[]),
+            Some(timeout),
+        )
+        .unwrap();
+        assert_eq!(result.parts[0].failure, Some(Failure::Timeout(timeout)));
+        // the second part still runs on a fresh worker
+        assert_eq!(result.parts[1].answer, Answer::Number(7500));
+    }
+}